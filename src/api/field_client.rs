@@ -0,0 +1,339 @@
+use tokio::time::sleep;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use reqwest::{Client as ReqwestClient, Method, StatusCode};
+use crate::models::field::Field;
+use crate::models::halo_field::{HaloField, CreatedField};
+use crate::error::{Result, CustomError, ApiErrorKind};
+use crate::config::Config;
+use crate::auth::client::AuthClient;
+use crate::transformers::JsonTransformer;
+use super::rate_limit::RateLimiter;
+use super::retry::RetryPolicy;
+use log::{debug, warn};
+
+const CREATE_PATH: &str = "fieldinfo";
+
+/// A request exactly as `create_field` would send it, with the token redacted.
+pub struct RenderedRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl std::fmt::Display for RenderedRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{} {}", self.method, self.url)?;
+        for (name, value) in &self.headers {
+            writeln!(f, "{}: {}", name, value)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{}", self.body)
+    }
+}
+
+/// Result of an API call together with the number of attempts, the time it took and the body sent.
+pub struct ApiOutcome<T> {
+    pub attempts: u32,
+    pub elapsed: Duration,  // including retry delays
+    pub payload: Option<String>,  // `None` when no body was sent
+    pub result: Result<T>,
+}
+
+impl<T> ApiOutcome<T> {
+    // A request that could not be built and was never sent
+    fn not_sent(error: CustomError) -> Self {
+        ApiOutcome { attempts: 0, elapsed: Duration::ZERO, payload: None, result: Err(error) }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ApiOutcome<U> {
+        ApiOutcome { attempts: self.attempts, elapsed: self.elapsed, payload: self.payload, result: self.result.map(f) }
+    }
+}
+
+// A single failed attempt and whether the retry policy may try again
+struct AttemptError {
+    error: CustomError,
+    retryable: bool,
+    retry_after: Option<Duration>,
+    unauthorized: bool,
+    may_have_applied: bool,  // the request may have reached Halo before it failed
+}
+
+impl AttemptError {
+    fn fatal(error: CustomError) -> Self {
+        Self {
+            error,
+            retryable: false,
+            retry_after: None,
+            unauthorized: false,
+            may_have_applied: false,
+        }
+    }
+}
+
+// Everything needed to send, and if necessary resend, one API call
+struct ApiRequest<'a> {
+    method: Method,
+    path: &'a str,
+    body: Option<&'a str>,
+    description: &'a str,
+    status_error: &'a dyn Fn(String) -> ApiErrorKind,
+    created_name: Option<&'a str>,  // name of the field a create adds, looked up before it is resent
+}
+
+pub struct FieldClient {
+    config: Config,
+    http_client: ReqwestClient,
+    auth_client: Arc<AuthClient>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl FieldClient {
+    pub fn new(config: Config, auth_client: Arc<AuthClient>, rate_limiter: Arc<RateLimiter>) -> Self {
+        let retry_policy = RetryPolicy::from_config(&config);
+        Self {
+            config,
+            http_client: ReqwestClient::new(),
+            auth_client,
+            retry_policy,
+            rate_limiter,
+        }
+    }
+
+    /// Builds the creation request for a field without authenticating or sending anything.
+    pub fn render_create_request(config: &Config, field: &Field) -> Result<RenderedRequest> {
+        Ok(RenderedRequest {
+            method: Method::POST,
+            url: Self::endpoint(config, CREATE_PATH),
+            headers: vec![
+                ("Authorization", "<redacted>".to_string()),
+                ("Content-Type", "application/json".to_string()),
+            ],
+            body: JsonTransformer::to_json(std::slice::from_ref(field))?,
+        })
+    }
+
+    fn endpoint(config: &Config, path: &str) -> String {
+        format!("{}/{}", config.api_url, path)
+    }
+
+    /// The created field is `None` when Halo accepted the field but its response could not be read.
+    pub async fn create_field(&self, field: &Field) -> ApiOutcome<Option<CreatedField>> {
+        let json = match JsonTransformer::to_json(std::slice::from_ref(field)) {
+            Ok(json) => json,
+            Err(e) => return ApiOutcome::not_sent(e.into()),
+        };
+
+        let description = format!("field '{}'", field.label);
+        let status_error = |message: String| ApiErrorKind::FieldCreationFailed(field.label.clone(), message);
+
+        let outcome = self.send_with_retry(ApiRequest {
+            method: Method::POST,
+            path: CREATE_PATH,
+            body: Some(&json),
+            description: &description,
+            status_error: &status_error,
+            created_name: Some(&field.name),
+        }).await;
+
+        // The field exists at this point, an unexpected response must not turn it into a failure
+        outcome.map(|body| match CreatedField::parse(&body) {
+            Ok(created) => {
+                debug!("Field '{}' created as {} (id {}) with {} value(s)",
+                    field.label, created.name, created.id, created.values.len()
+                );
+                Some(created)
+            },
+            Err(e) => {
+                warn!("Field '{}' was created but its id could not be read from the response: {}", field.label, e);
+                None
+            },
+        })
+    }
+
+    pub async fn update_field(&self, field: &Field, id: i64) -> ApiOutcome<()> {
+        let json = match JsonTransformer::to_update_json(field, id) {
+            Ok(json) => json,
+            Err(e) => return ApiOutcome::not_sent(e.into()),
+        };
+
+        let description = format!("update of field '{}'", field.label);
+        let status_error = |message: String| ApiErrorKind::FieldUpdateFailed(field.label.clone(), message);
+
+        let outcome = self.send_with_retry(ApiRequest {
+            method: Method::POST,
+            path: "fieldinfo",
+            body: Some(&json),
+            description: &description,
+            status_error: &status_error,
+            created_name: None,
+        }).await;
+
+        outcome.map(|_| ())
+    }
+
+    pub async fn delete_field(&self, field: &HaloField) -> ApiOutcome<()> {
+        let path = format!("fieldinfo/{}", field.id);
+        let description = format!("deletion of field '{}'", field.name);
+        let status_error = |message: String| ApiErrorKind::FieldDeleteFailed(field.name.clone(), message);
+
+        let outcome = self.send_with_retry(ApiRequest {
+            method: Method::DELETE,
+            path: &path,
+            body: None,
+            description: &description,
+            status_error: &status_error,
+            created_name: None,
+        }).await;
+
+        outcome.map(|_| ())
+    }
+
+    /// Fetches every custom field currently defined on the Halo instance.
+    pub async fn list_fields(&self) -> Result<Vec<HaloField>> {
+        let status_error = ApiErrorKind::FieldListFailed;
+
+        let body = self.send_with_retry(ApiRequest {
+            method: Method::GET,
+            path: "fieldinfo",
+            body: None,
+            description: "existing fields",
+            status_error: &status_error,
+            created_name: None,
+        }).await.result?;
+
+        serde_json::from_str(&body).map_err(|e|
+            CustomError::ApiError(ApiErrorKind::InvalidResponse(format!("Failed to parse field list: {}", e)))
+        )
+    }
+
+    async fn send_with_retry(&self, request: ApiRequest<'_>) -> ApiOutcome<String> {
+        let started = Instant::now();
+        let payload = request.body.map(str::to_string);
+        let mut attempts = 0;
+        let mut token_refreshed = false;
+        loop {
+            attempts += 1;
+
+            let failure = match self.send_request(&request).await {
+                Ok(body) => return ApiOutcome { attempts, elapsed: started.elapsed(), payload, result: Ok(body) },
+                Err(failure) => failure,
+            };
+
+            // The token may have been revoked or expired early, refresh it once and replay
+            if failure.unauthorized && !token_refreshed {
+                token_refreshed = true;
+                warn!("Request for {} was unauthorized, refreshing token", request.description);
+                if let Err(e) = self.auth_client.refresh_token().await {
+                    return ApiOutcome { attempts, elapsed: started.elapsed(), payload, result: Err(e) };
+                }
+                continue;
+            }
+
+            if !failure.retryable || attempts >= self.retry_policy.max_attempts {
+                return ApiOutcome { attempts, elapsed: started.elapsed(), payload, result: Err(failure.error) };
+            }
+
+            // A create that may have gone through is only sent again once Halo shows it doesn't exist
+            if let Some(name) = request.created_name.filter(|_| failure.may_have_applied) {
+                match self.find_created(name).await {
+                    Ok(Some(body)) => {
+                        warn!("{} failed but the field exists on the instance, it is not sent again", request.description);
+                        return ApiOutcome { attempts, elapsed: started.elapsed(), payload, result: Ok(body) };
+                    },
+                    Ok(None) => {},
+                    Err(e) => {
+                        warn!("Cannot check whether {} was created, it is not sent again: {}", request.description, e);
+                        return ApiOutcome { attempts, elapsed: started.elapsed(), payload, result: Err(failure.error) };
+                    },
+                }
+            }
+
+            let delay = failure.retry_after
+                .unwrap_or_else(|| self.retry_policy.backoff_delay(attempts));
+            warn!("Attempt {} of {} for {} failed: {}. Retrying in {}ms",
+                attempts,
+                self.retry_policy.max_attempts,
+                request.description,
+                failure.error,
+                delay.as_millis()
+            );
+            sleep(delay).await;
+        }
+    }
+
+    /// The field with the given name as a create response body, if it is on the instance.
+    async fn find_created(&self, name: &str) -> Result<Option<String>> {
+        // Boxed because listing the fields goes through `send_with_retry` again
+        let existing = Box::pin(self.list_fields()).await?;
+        existing.iter()
+            .find(|halo_field| halo_field.matches_name(name))
+            .map(|halo_field| serde_json::to_string(halo_field).map_err(CustomError::from))
+            .transpose()
+    }
+
+    async fn send_request(&self, request: &ApiRequest<'_>) -> std::result::Result<String, AttemptError> {
+        let auth_header = self.auth_client.get_valid_token().await
+            .map_err(AttemptError::fatal)?;
+
+        self.rate_limiter.acquire().await;
+
+        let endpoint = Self::endpoint(&self.config, request.path);
+        debug!("Sending {} request for: {}", request.method, request.description);
+
+        let mut builder = self.http_client
+            .request(request.method.clone(), &endpoint)
+            .header("Authorization", auth_header);
+
+        if let Some(body) = request.body {
+            builder = builder
+                .header("Content-Type", "application/json")
+                .body(body.to_string());
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| AttemptError {
+                retryable: RetryPolicy::is_retryable_error(&e),
+                retry_after: None,
+                unauthorized: false,
+                // A refused connection never reached Halo
+                may_have_applied: !e.is_connect(),
+                error: CustomError::ApiError(ApiErrorKind::NetworkError(e.to_string())),
+            })?;
+
+        self.rate_limiter.observe(response.headers()).await;
+
+        let status = response.status();
+        if !status.is_success() {
+            // Never wait longer than the retry policy allows, whatever the server asks for
+            let retry_after = RetryPolicy::retry_after(response.headers())
+                .map(|delay| delay.min(self.retry_policy.max_delay));
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                if let Some(delay) = retry_after {
+                    self.rate_limiter.pause_for(delay).await;
+                }
+            }
+            let error_text = response.text().await
+                .unwrap_or_else(|_| "Failed to get error response".to_string());
+
+            return Err(AttemptError {
+                retryable: RetryPolicy::is_retryable_status(status),
+                retry_after,
+                unauthorized: status == StatusCode::UNAUTHORIZED,
+                may_have_applied: status.is_server_error(),
+                error: CustomError::ApiError((request.status_error)(
+                    format!("Status: {}, Error: {}", status, error_text)
+                )),
+            });
+        }
+
+        response.text().await.map_err(|e| AttemptError::fatal(
+            CustomError::ApiError(ApiErrorKind::InvalidResponse(format!("Failed to read response: {}", e)))
+        ))
+    }
+}
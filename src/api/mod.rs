@@ -0,0 +1,3 @@
+pub mod field_client;
+pub mod rate_limit;
+pub mod retry;
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use log::{debug, warn};
+use reqwest::header::HeaderMap;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use crate::config::Config;
+
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RESET_HEADER: &str = "x-ratelimit-reset";
+
+// Anything above this is treated as a unix timestamp rather than a delay in seconds
+const RESET_EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+/// Sliding window limiter shared by every client talking to the same Halo instance.
+///
+/// Halo allows a fixed number of requests per rolling window (700 per 5 minutes by
+/// default). Rate-limit headers returned by the server tighten the budget further.
+pub struct RateLimiter {
+    max_requests: usize,
+    window: Duration,
+    state: Mutex<WindowState>,
+}
+
+#[derive(Default)]
+struct WindowState {
+    sent: VecDeque<Instant>,
+    paused_until: Option<Instant>,
+    server_budget: Option<ServerBudget>,
+}
+
+struct ServerBudget {
+    remaining: u32,
+    resets_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: usize, window: Duration) -> Self {
+        Self {
+            max_requests: max_requests.max(1),
+            window,
+            state: Mutex::new(WindowState::default()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.rate_limit_requests,
+            Duration::from_secs(config.rate_limit_window_secs),
+        )
+    }
+
+    /// Waits until another request fits into the window and records it as sent.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+
+                while state.sent.front().is_some_and(|sent| now.duration_since(*sent) >= self.window) {
+                    state.sent.pop_front();
+                }
+
+                if state.server_budget.as_ref().is_some_and(|budget| now >= budget.resets_at) {
+                    state.server_budget = None;
+                }
+
+                match self.next_slot(&state, now) {
+                    Some(ready_at) => ready_at.duration_since(now),
+                    None => {
+                        state.sent.push_back(now);
+                        if let Some(budget) = state.server_budget.as_mut() {
+                            budget.remaining = budget.remaining.saturating_sub(1);
+                        }
+                        return;
+                    }
+                }
+            };
+
+            debug!("Rate limit reached, waiting {}ms", wait.as_millis());
+            sleep(wait).await;
+        }
+    }
+
+    // Earliest instant a request may be sent, or None if it may go out now
+    fn next_slot(&self, state: &WindowState, now: Instant) -> Option<Instant> {
+        if let Some(until) = state.paused_until.filter(|until| *until > now) {
+            return Some(until);
+        }
+
+        if let Some(budget) = state.server_budget.as_ref().filter(|budget| budget.remaining == 0) {
+            return Some(budget.resets_at);
+        }
+
+        if state.sent.len() >= self.max_requests {
+            return state.sent.front().map(|oldest| *oldest + self.window);
+        }
+
+        None
+    }
+
+    /// Pauses all callers, typically because the server answered 429 with `Retry-After`.
+    pub async fn pause_for(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut state = self.state.lock().await;
+        if state.paused_until.is_none_or(|current| current < until) {
+            warn!("Pausing requests for {}ms as requested by the server", delay.as_millis());
+            state.paused_until = Some(until);
+        }
+    }
+
+    /// Adopts the server's view of the remaining budget when it sends
+    /// `X-RateLimit-Remaining` / `X-RateLimit-Reset` headers.
+    pub async fn observe(&self, headers: &HeaderMap) {
+        let remaining = Self::header_number(headers, REMAINING_HEADER);
+        let reset = Self::header_number(headers, RESET_HEADER);
+
+        let (Some(remaining), Some(reset)) = (remaining, reset) else {
+            return;
+        };
+
+        let reset_in = if reset > RESET_EPOCH_THRESHOLD {
+            Duration::from_secs(reset.saturating_sub(Utc::now().timestamp().max(0) as u64))
+        } else {
+            Duration::from_secs(reset)
+        };
+
+        let mut state = self.state.lock().await;
+        state.server_budget = Some(ServerBudget {
+            remaining: remaining.min(u32::MAX as u64) as u32,
+            resets_at: Instant::now() + reset_in,
+        });
+    }
+
+    fn header_number(headers: &HeaderMap, name: &str) -> Option<u64> {
+        headers.get(name)?.to_str().ok()?.trim().parse().ok()
+    }
+}
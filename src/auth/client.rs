@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::api::rate_limit::RateLimiter;
 use crate::config::Config;
 use crate::error::{Result, CustomError, AuthErrorKind};
 use super::token::AuthToken;
@@ -31,14 +32,16 @@ pub struct AuthClient {
     config: Config,
     http_client: ReqwestClient,
     current_token: Arc<Mutex<Option<AuthToken>>>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 impl AuthClient {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config, rate_limiter: Arc<RateLimiter>) -> Self {
         Self {
             config,
             http_client: ReqwestClient::new(),
             current_token: Arc::new(Mutex::new(None)),
+            rate_limiter,
         }
     }
 
@@ -59,6 +62,16 @@ impl AuthClient {
         Ok(token_guard.as_ref().unwrap().header_value())
     }
 
+    /// Discards the cached token and fetches a new one, e.g. after the API answered 401.
+    pub async fn refresh_token(&self) -> Result<String> {
+        let mut token_guard = self.current_token.lock().await;
+
+        let new_token = self.fetch_new_token().await?;
+        *token_guard = Some(new_token);
+
+        Ok(token_guard.as_ref().unwrap().header_value())
+    }
+
     async fn fetch_new_token(&self) -> Result<AuthToken> {
         let token_request = TokenRequest {
             client_id: self.config.client_id.clone(),
@@ -66,6 +79,8 @@ impl AuthClient {
             grant_type: "client_credentials".to_string(),
         };
 
+        self.rate_limiter.acquire().await;
+
         let response = self.http_client
             .post(&self.config.token_url)
             .header("Content-Type", "application/x-www-form-urlencoded")
@@ -83,6 +98,8 @@ impl AuthClient {
                 CustomError::AuthError(AuthErrorKind::TokenFetchFailed(e.to_string()))
             })?;
 
+        self.rate_limiter.observe(response.headers()).await;
+
         // Get the status before consuming the response
         let status = response.status();
         println!("Debug - Response received: {}", if status.is_success() { "Success" } else { "Error" });
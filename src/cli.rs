@@ -0,0 +1,132 @@
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+use crate::models::entity::Entity;
+use crate::screens::RunMode;
+
+/// Bulk creation of custom fields in Halo from a CSV file.
+///
+/// Without a subcommand the interactive menu is shown, which requires a terminal.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Input file, overrides SOURCE_FILE_NAME
+    #[arg(long, global = true)]
+    pub file: Option<String>,
+
+    /// Workbook sheet to read, by name or 1-based position, overrides SOURCE_SHEET
+    #[arg(long, global = true)]
+    pub sheet: Option<String>,
+
+    /// Answer yes to every confirmation prompt
+    #[arg(long, short = 'y', global = true)]
+    pub yes: bool,
+
+    /// Entity for fields without a usage, by name or id, overrides DEFAULT_USAGE
+    #[arg(long, global = true, value_parser = parse_entity)]
+    pub default_usage: Option<Entity>,
+
+    /// Write validation errors to this file (`.json` or `.csv`), overrides VALIDATION_REPORT_FILE
+    #[arg(long, global = true)]
+    pub validation_report: Option<String>,
+
+    /// Write import results to this file (`.json`, `.xml` for JUnit or `.csv`), overrides IMPORT_REPORT_FILE
+    #[arg(long, global = true)]
+    pub report: Option<String>,
+
+    /// Naming rules for field names and labels (YAML or JSON), overrides LINT_RULES_FILE
+    #[arg(long, global = true)]
+    pub lint_rules: Option<String>,
+
+    /// Configuration file to load instead of `.env`
+    #[arg(long, global = true)]
+    pub env_file: Option<String>,
+
+    /// Minimum level written to the terminal and the log file
+    #[arg(long, global = true, default_value = "info")]
+    pub log_level: LevelFilter,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Import all fields from the input file
+    Import {
+        /// Print the requests that would be sent instead of sending them
+        #[arg(long)]
+        dry_run: bool,
+        /// Also write the dry run requests to this file
+        #[arg(long, requires = "dry_run")]
+        dry_run_output: Option<String>,
+        /// Continue an interrupted run with its pending and failed fields
+        #[arg(long, value_name = "RUN_ID", conflicts_with = "dry_run")]
+        resume: Option<String>,
+    },
+    /// Validate the input file without contacting Halo
+    Validate,
+    /// Review and import fields one at a time
+    Debug {
+        /// Continue an interrupted run with its pending and failed fields
+        #[arg(long, value_name = "RUN_ID")]
+        resume: Option<String>,
+    },
+    /// Import only the fields that failed in the previous run
+    RetryFailed {
+        /// Failed fields file, overrides FAILED_FILE_NAME
+        #[arg(long)]
+        failed_file: Option<String>,
+    },
+    /// Show the changes needed to sync the instance with the input file
+    Plan,
+    /// Apply the changes needed to sync the instance with the input file
+    Apply {
+        /// Delete fields missing from the input file without asking, implies SYNC_DELETE_ORPHANS
+        #[arg(long)]
+        delete_orphans: bool,
+    },
+    /// Export the instance's fields to a CSV file
+    Export {
+        /// Output file, overrides EXPORT_FILE_NAME
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Clone fields from another instance
+    Clone {
+        /// Profile of the source instance, overrides CLONE_SOURCE_ENV_FILE
+        #[arg(long)]
+        source_env_file: Option<String>,
+        /// Only clone fields whose name matches, `*` and `?` are wildcards
+        #[arg(long)]
+        pattern: Option<String>,
+    },
+}
+
+impl Command {
+    pub fn run_mode(&self) -> RunMode {
+        match self {
+            Command::Import { .. } => RunMode::Import,
+            Command::Validate => RunMode::Validate,
+            Command::Debug { .. } => RunMode::Debug,
+            Command::RetryFailed { .. } => RunMode::RetryFailed,
+            Command::Plan => RunMode::Plan,
+            Command::Apply { .. } => RunMode::Apply,
+            Command::Export { .. } => RunMode::Export,
+            Command::Clone { .. } => RunMode::Clone,
+        }
+    }
+
+    pub fn resume(&self) -> Option<&str> {
+        match self {
+            Command::Import { resume, .. } | Command::Debug { resume } => resume.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn parse_entity(value: &str) -> Result<Entity, String> {
+    Entity::parse(value).ok_or_else(|| {
+        let valid: Vec<_> = Entity::ALL.iter().map(|entity| entity.display_name()).collect();
+        format!("unknown usage '{}', expected an id or one of: {}", value, valid.join(", "))
+    })
+}
@@ -1,25 +1,61 @@
 use dotenv::dotenv;
+use std::collections::HashMap;
 use std::env;
+use std::path::Path;
+use std::str::FromStr;
 use url::Url;
 use crate::error::{Result, CustomError, ConfigErrorKind};
+use crate::models::entity::Entity;
+use crate::models::halo_field::HALO_NAME_PREFIX;
 
 #[derive(Debug, Clone)]
 pub struct Config {
-    #[allow(dead_code)]
     pub base_url: String,
-    #[allow(dead_code)]
     pub tenant: String,
     pub token_url: String,
     pub api_url: String,
     pub client_id: String,
     pub client_secret: String,
     pub source_file_name: String,
+    pub source_sheet: Option<String>,
+    pub export_file_name: String,
+    pub failed_file_name: String,
+    pub default_usage: Entity,
+    pub validation_report_file: Option<String>,
+    pub import_report_file: Option<String>,
+    pub lint_rules_file: Option<String>,
+    pub name_prefix: Option<String>,
+    pub name_max_length: usize,
+    pub retry_max_attempts: u32,
+    pub retry_base_delay_ms: u64,
+    pub retry_max_delay_ms: u64,
+    pub retry_jitter_ms: u64,
+    pub rate_limit_requests: usize,
+    pub rate_limit_window_secs: u64,
+    pub sync_delete_orphans: bool,
+    pub clone_source_env_file: String,
+}
+
+// Where configuration values are looked up: the process environment (populated
+// from `.env`) or a separate profile file that must not leak into the environment
+enum EnvSource {
+    Process,
+    Profile(HashMap<String, String>),
+}
+
+impl EnvSource {
+    fn get(&self, key: &str) -> Option<String> {
+        match self {
+            EnvSource::Process => env::var(key).ok(),
+            EnvSource::Profile(vars) => vars.get(key).cloned(),
+        }
+    }
 }
 
 impl Config {
-    fn get_env_var(key: &str, allow_empty: bool) -> Result<String> {
-        let value = env::var(key)
-            .map_err(|_| CustomError::ConfigError(ConfigErrorKind::MissingEnvVar(key.to_string())))?;
+    fn get_env_var(source: &EnvSource, key: &str, allow_empty: bool) -> Result<String> {
+        let value = source.get(key)
+            .ok_or_else(|| CustomError::ConfigError(ConfigErrorKind::MissingEnvVar(key.to_string())))?;
         
         if !allow_empty && value.trim().is_empty() {
             return Err(CustomError::ConfigError(ConfigErrorKind::EmptyEnvVar(key.to_string())));
@@ -28,9 +64,18 @@ impl Config {
         Ok(value.trim().to_string())
     }
 
+    fn get_optional_env_var<T: FromStr>(source: &EnvSource, key: &str, default: T) -> Result<T> {
+        match source.get(key) {
+            Some(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_|
+                CustomError::ConfigError(ConfigErrorKind::InvalidValue(key.to_string(), value.trim().to_string()))
+            ),
+            _ => Ok(default),
+        }
+    }
+
     fn validate_url(url: &str, _field_name: &str) -> Result<String> {
         // Ensure URL starts with https:// and is valid
-        let url_actual = Url::parse(&url).map_err(|_| 
+        let url_actual = Url::parse(url).map_err(|_| 
             CustomError::ConfigError(ConfigErrorKind::InvalidUrlFormat(url.to_string()))
         )?;
         if url_actual.scheme() != "https" {
@@ -49,23 +94,105 @@ impl Config {
         }
     }
 
-    pub fn new() -> Result<Self> {
-        // Load .env file
-        dotenv().map_err(|_| 
-            CustomError::ConfigError(ConfigErrorKind::MissingEnvFile)
-        )?;
+    pub fn new(env_file: Option<&str>) -> Result<Self> {
+        // Load .env file, or the file given on the command line
+        match env_file {
+            Some(path) => dotenv::from_path(path).map_err(|_|
+                CustomError::ConfigError(ConfigErrorKind::MissingProfileFile(path.to_string()))
+            )?,
+            None => {
+                dotenv().map_err(|_| 
+                    CustomError::ConfigError(ConfigErrorKind::MissingEnvFile)
+                )?;
+            },
+        }
+
+        Self::load(&EnvSource::Process, true)
+    }
+
+    /// Loads a second instance profile (e.g. `.env.sandbox`) without touching the
+    /// process environment, so two instances can be configured side by side.
+    // `from_path` would export the profile into the environment and mix it with `.env`
+    #[allow(deprecated)]
+    pub fn from_env_file(path: &str) -> Result<Self> {
+        let missing_profile = || CustomError::ConfigError(ConfigErrorKind::MissingProfileFile(path.to_string()));
+
+        let vars = dotenv::from_path_iter(Path::new(path))
+            .map_err(|_| missing_profile())?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()
+            .map_err(|_| missing_profile())?;
 
+        Self::load(&EnvSource::Profile(vars), false)
+    }
+
+    fn load(source: &EnvSource, require_source_file: bool) -> Result<Self> {
         // Get and validate required variables
-        let raw_base_url = Self::get_env_var("BASE_URL", false)?;
+        let raw_base_url = Self::get_env_var(source, "BASE_URL", false)?;
         let base_url = Self::validate_url(&raw_base_url, "BASE_URL")?;
         
         // Tenant can be empty
-        let tenant = Self::get_env_var("TENANT", true)?;
+        let tenant = Self::get_env_var(source, "TENANT", true)?;
         
         // Get and validate other required variables
-        let client_id = Self::get_env_var("CLIENT_ID", false)?;
-        let client_secret = Self::get_env_var("CLIENT_SECRET", false)?;
-        let source_file_name = Self::get_env_var("SOURCE_FILE_NAME", false)?;
+        let client_id = Self::get_env_var(source, "CLIENT_ID", false)?;
+        let client_secret = Self::get_env_var(source, "CLIENT_SECRET", false)?;
+        // Profiles used only as a clone source don't need an input file
+        let source_file_name = if require_source_file {
+            Self::get_env_var(source, "SOURCE_FILE_NAME", false)?
+        } else {
+            Self::get_optional_env_var(source, "SOURCE_FILE_NAME", String::new())?
+        };
+        // Workbook sheet to read, by name or 1-based position, the first sheet when unset
+        let source_sheet = source.get("SOURCE_SHEET")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        let export_file_name = Self::get_optional_env_var(source, "EXPORT_FILE_NAME", "export.csv".to_string())?;
+        // Fields that failed to import, kept for the retry-failed command
+        let failed_file_name = Self::get_optional_env_var(source, "FAILED_FILE_NAME", "failed.csv".to_string())?;
+        // It is always written as CSV, and read back by its extension on retry
+        let is_csv = Path::new(&failed_file_name).extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        if !is_csv {
+            return Err(CustomError::ConfigError(ConfigErrorKind::InvalidValue("FAILED_FILE_NAME".to_string(), failed_file_name)));
+        }
+        // Entity for fields without a usage of their own, by name or id
+        let default_usage = Self::get_optional_env_var(source, "DEFAULT_USAGE", Entity::default())?;
+        let validation_report_file = source.get("VALIDATION_REPORT_FILE")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        // Results of an import for CI systems, format picked by extension
+        let import_report_file = source.get("IMPORT_REPORT_FILE")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        // Naming policy checked while reading the input file, no rules apply when unset
+        let lint_rules_file = source.get("LINT_RULES_FILE")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        // Used for names generated from labels, the prefix falls back to the `name_prefix` lint rule
+        let name_prefix = source.get("NAME_PREFIX")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        // Halo adds its own prefix on creation, including it here would double it
+        if let Some(prefix) = name_prefix.as_ref().filter(|prefix| prefix.starts_with(HALO_NAME_PREFIX)) {
+            return Err(CustomError::ConfigError(ConfigErrorKind::InvalidValue("NAME_PREFIX".to_string(), prefix.clone())));
+        }
+        let name_max_length = Self::get_optional_env_var(source, "NAME_MAX_LENGTH", 30)?;
+
+        // Optional retry tuning, defaults suit a typical Halo cloud instance
+        let retry_max_attempts = Self::get_optional_env_var(source, "RETRY_MAX_ATTEMPTS", 5)?;
+        let retry_base_delay_ms = Self::get_optional_env_var(source, "RETRY_BASE_DELAY_MS", 1000)?;
+        let retry_max_delay_ms = Self::get_optional_env_var(source, "RETRY_MAX_DELAY_MS", 30000)?;
+        let retry_jitter_ms = Self::get_optional_env_var(source, "RETRY_JITTER_MS", 250)?;
+
+        // Halo allows 700 requests per 5 minutes across all endpoints
+        let rate_limit_requests = Self::get_optional_env_var(source, "RATE_LIMIT_REQUESTS", 700)?;
+        let rate_limit_window_secs = Self::get_optional_env_var(source, "RATE_LIMIT_WINDOW_SECS", 300)?;
+
+        // Sync mode only reports orphaned fields unless deletion is explicitly enabled
+        let sync_delete_orphans = Self::get_optional_env_var(source, "SYNC_DELETE_ORPHANS", false)?;
+
+        // Profile of the instance fields are cloned from
+        let clone_source_env_file = Self::get_optional_env_var(source, "CLONE_SOURCE_ENV_FILE", ".env.source".to_string())?;
 
         // Build and validate derived URLs
         let api_url = format!("{}/api", &base_url);
@@ -80,6 +207,23 @@ impl Config {
             client_id,
             client_secret,
             source_file_name,
+            source_sheet,
+            export_file_name,
+            failed_file_name,
+            default_usage,
+            validation_report_file,
+            import_report_file,
+            lint_rules_file,
+            name_prefix,
+            name_max_length,
+            retry_max_attempts,
+            retry_base_delay_ms,
+            retry_max_delay_ms,
+            retry_jitter_ms,
+            rate_limit_requests,
+            rate_limit_window_secs,
+            sync_delete_orphans,
+            clone_source_env_file,
         })
     }
 }
\ No newline at end of file
@@ -1,31 +1,91 @@
 use std::error::Error;
 use std::fmt;
+use crate::models::entity::Entity;
+use crate::models::field_type::{FieldType, InputType};
+use crate::models::validation_report::ValidationReport;
 
 #[derive(Debug)]
 pub struct FieldError {
-    pub row: usize,
+    pub location: RecordLocation,
+    pub column: String,
+    pub cell: Option<String>,  // spreadsheet cell reference, e.g. `Fields!C5`
+    pub value: String,
     pub error: FieldErrorKind,
 }
 
+/// Where a field definition sits in the input file, so errors point at the right place.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RecordLocation {
+    Row(usize),    // 0-based data row of a CSV file
+    Entry(usize),  // 0-based position in a JSON or YAML list
+    Sheet { sheet: String, row: u32 },  // 1-based row number of a workbook sheet
+}
+
+impl RecordLocation {
+    // The number a person sees when opening the file
+    pub fn number(&self) -> usize {
+        match self {
+            // +2 because: +1 for header row, +1 because rows start at 0
+            RecordLocation::Row(row) => row + 2,
+            RecordLocation::Entry(index) => index + 1,
+            RecordLocation::Sheet { row, .. } => *row as usize,
+        }
+    }
+
+    // Lower-case form for use inside a message, e.g. "already used in row 4"
+    pub fn in_sentence(&self) -> String {
+        match self {
+            RecordLocation::Row(_) => format!("row {}", self.number()),
+            RecordLocation::Entry(_) => format!("field {}", self.number()),
+            RecordLocation::Sheet { sheet, row } => format!("sheet '{}' row {}", sheet, row),
+        }
+    }
+}
+
+impl fmt::Display for RecordLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordLocation::Row(_) => write!(f, "Row {}", self.number()),
+            RecordLocation::Entry(_) => write!(f, "Field {}", self.number()),
+            RecordLocation::Sheet { sheet, row } => write!(f, "Sheet '{}' row {}", sheet, row),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum FieldErrorKind {
+    #[allow(dead_code)]
     ParseError(String),
     RequiredFieldEmpty(String),
     InvalidFieldName(String),
     InvalidLabel(String),
-    InvalidTypeId(String),
-    InvalidInputType(String),
+    InvalidTypeId(String, Option<FieldType>),        // (value, suggestion)
+    InvalidInputType(FieldType, Option<InputType>),  // (field type, suggestion)
     MissingOptions(String),
+    InvalidFlag(String),
+    InvalidOptionOrder(String),
+    DuplicateOptions(Vec<String>),
+    DuplicateName(RecordLocation),              // (first location)
+    NameCollision(String, RecordLocation),      // (first name, first location)
+    DuplicateLabel(RecordLocation),             // (first location)
+    InvalidUsage(String, Option<Entity>),     // (value, suggestion)
+    FieldTypeNotAllowed(FieldType, Entity),
+    LintViolation(&'static str, String),      // (rule, message)
+    NameNotGenerated(String, String),         // (label, reason)
 }
 
 #[derive(Debug)]
 pub enum ConfigErrorKind {
     MissingEnvFile,
+    MissingProfileFile(String),
     MissingEnvVar(String),
     #[allow(dead_code)]
     InvalidUrl(String),
     EmptyEnvVar(String),
     InvalidUrlFormat(String),
+    InvalidValue(String, String),  // (variable, value)
+    InvalidLintRules(String, String),  // (path, error_message)
+    UnknownReportFormat(String),       // (path)
     JsonError(String),
 }
 
@@ -53,9 +113,10 @@ pub enum IOErrorKind {
 #[derive(Debug)]
 pub enum ApiErrorKind {
     FieldCreationFailed(String, String),  // (field_label, error_message)
-    #[allow(dead_code)]
+    FieldListFailed(String),
+    FieldUpdateFailed(String, String),    // (field_label, error_message)
+    FieldDeleteFailed(String, String),    // (field_name, error_message)
     InvalidResponse(String),
-    #[allow(dead_code)]
     NetworkError(String),
 }
 
@@ -63,7 +124,11 @@ pub enum ApiErrorKind {
 pub enum CustomError {
     CsvError(csv::Error),
     MissingColumn(String),
-    FieldError(FieldError),
+    InvalidSourceFile(String, String),  // (path, error_message)
+    InvalidRunJournal(String, String),  // (run_id, error_message)
+    NotInteractive(String),
+    FieldError(Box<FieldError>),
+    ValidationFailed(ValidationReport),
     ConfigError(ConfigErrorKind),
     AuthError(AuthErrorKind),
     IOError(IOErrorKind),
@@ -76,44 +141,34 @@ impl fmt::Display for CustomError {
             CustomError::CsvError(e) => write!(f, "CSV error: {}", e),
             CustomError::MissingColumn(col) => write!(
                 f, 
-                "Required column '{}' is missing from the CSV file. Please check your column headers", 
+                "Required column '{}' is missing from the input file. Please check your column headers", 
                 col
             ),
+            CustomError::NotInteractive(msg) => write!(f, "No answer can be read from the terminal: {}", msg),
+            CustomError::InvalidRunJournal(run_id, error) => write!(
+                f,
+                "Cannot resume run '{}': {}",
+                run_id,
+                error
+            ),
+            CustomError::InvalidSourceFile(path, error) => write!(
+                f,
+                "Could not read field definitions from '{}': {}",
+                path,
+                error
+            ),
             CustomError::FieldError(error) => write!(
                 f, 
-                "Error in row {}: {}", 
-                error.row + 2,  // +2 because: +1 for header row, +1 because rows start at 0
-                match &error.error {
-                    FieldErrorKind::ParseError(field) => format!(
-                        "Failed to parse '{}'. Please ensure the value is in the correct format", 
-                        field
-                    ),
-                    FieldErrorKind::RequiredFieldEmpty(field) => format!(
-                        "The '{}' field cannot be empty. Please provide a value", 
-                        field
-                    ),
-                    FieldErrorKind::InvalidFieldName(name) => format!(
-                        "Field name '{}' is invalid. Names must contain only letters and numbers", 
-                        name
-                    ),
-                    FieldErrorKind::InvalidLabel(label) => format!(
-                        "Invalid label: {}. Labels must contain visible characters", 
-                        label
-                    ),
-                    FieldErrorKind::InvalidTypeId(type_id) => format!(
-                        "Invalid type_id: {}.\n\nValid values are:\n0 (Text)\n1 (Memo)\n2 (Single Selection\n3 (Multiple Selection)\n4 (Date)\n5 (Time)\n6 (Checkbox)\n10 (Rich)", 
-                        type_id
-                    ),
-                    FieldErrorKind::InvalidInputType(msg) => msg.clone(),
-                    FieldErrorKind::MissingOptions(msg) => format!(
-                        "{}. Please provide a comma-separated list of options", 
-                        msg
-                    ),
-                }
+                "Error in {}: {}", 
+                error.location.in_sentence(),
+                error.error
             ),
+            CustomError::ValidationFailed(report) => write!(f, "{}", report),
             CustomError::ConfigError(kind) => match kind {
                 ConfigErrorKind::MissingEnvFile => 
                     write!(f, "Failed to load .env file. Please ensure it exists in the project root"),
+                ConfigErrorKind::MissingProfileFile(path) =>
+                    write!(f, "Failed to load profile file '{}'. Please ensure it exists and is a valid .env file", path),
                 ConfigErrorKind::MissingEnvVar(var) => 
                     write!(f, "Required environment variable '{}' is must present", var),
                 ConfigErrorKind::InvalidUrl(url) => 
@@ -122,6 +177,12 @@ impl fmt::Display for CustomError {
                     write!(f, "Configuration value for '{}' must have value", field),
                 ConfigErrorKind::InvalidUrlFormat(url) =>
                     write!(f, "Invalid URL format for '{}'. URL must be a valid HTTPS URL", url),
+                ConfigErrorKind::InvalidValue(var, value) =>
+                    write!(f, "Invalid value '{}' for '{}'. Please check the expected format in the README", value, var),
+                ConfigErrorKind::InvalidLintRules(path, msg) =>
+                    write!(f, "Failed to load lint rules from '{}': {}", path, msg),
+                ConfigErrorKind::UnknownReportFormat(path) =>
+                    write!(f, "Unknown import report format for '{}'. Use a .json, .xml (JUnit) or .csv file", path),
                 ConfigErrorKind::JsonError(msg) => 
                     write!(f, "JSON serialization error: {}", msg),
             },
@@ -149,6 +210,12 @@ impl fmt::Display for CustomError {
             CustomError::ApiError(kind) => match kind {
                 ApiErrorKind::FieldCreationFailed(label, error) => 
                     write!(f, "Failed to create field '{}': {}", label, error),
+                ApiErrorKind::FieldListFailed(error) =>
+                    write!(f, "Failed to fetch existing fields: {}", error),
+                ApiErrorKind::FieldUpdateFailed(label, error) =>
+                    write!(f, "Failed to update field '{}': {}", label, error),
+                ApiErrorKind::FieldDeleteFailed(name, error) =>
+                    write!(f, "Failed to delete field '{}': {}", name, error),
                 ApiErrorKind::InvalidResponse(msg) => 
                     write!(f, "Invalid API response: {}", msg),
                 ApiErrorKind::NetworkError(msg) => 
@@ -210,10 +277,170 @@ impl fmt::Display for ApiErrorKind {
         match self {
             ApiErrorKind::FieldCreationFailed(label, error) => 
                 write!(f, "Failed to create field '{}': {}", label, error),
+            ApiErrorKind::FieldListFailed(error) =>
+                write!(f, "Failed to fetch existing fields: {}", error),
+            ApiErrorKind::FieldUpdateFailed(label, error) =>
+                write!(f, "Failed to update field '{}': {}", label, error),
+            ApiErrorKind::FieldDeleteFailed(name, error) =>
+                write!(f, "Failed to delete field '{}': {}", name, error),
             ApiErrorKind::InvalidResponse(msg) => 
                 write!(f, "Invalid API response: {}", msg),
             ApiErrorKind::NetworkError(msg) => 
                 write!(f, "Network error: {}", msg),
         }
     }
-}
\ No newline at end of file
+}
+
+impl FieldErrorKind {
+    // Stable identifier used in exported validation reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldErrorKind::ParseError(_) => "ParseError",
+            FieldErrorKind::RequiredFieldEmpty(_) => "RequiredFieldEmpty",
+            FieldErrorKind::InvalidFieldName(_) => "InvalidFieldName",
+            FieldErrorKind::InvalidLabel(_) => "InvalidLabel",
+            FieldErrorKind::InvalidTypeId(..) => "InvalidTypeId",
+            FieldErrorKind::InvalidInputType(..) => "InvalidInputType",
+            FieldErrorKind::MissingOptions(_) => "MissingOptions",
+            FieldErrorKind::InvalidFlag(_) => "InvalidFlag",
+            FieldErrorKind::InvalidOptionOrder(_) => "InvalidOptionOrder",
+            FieldErrorKind::DuplicateOptions(_) => "DuplicateOptions",
+            FieldErrorKind::DuplicateName(_) => "DuplicateName",
+            FieldErrorKind::NameCollision(..) => "NameCollision",
+            FieldErrorKind::DuplicateLabel(_) => "DuplicateLabel",
+            FieldErrorKind::InvalidUsage(..) => "InvalidUsage",
+            FieldErrorKind::FieldTypeNotAllowed(..) => "FieldTypeNotAllowed",
+            FieldErrorKind::LintViolation(..) => "LintViolation",
+            FieldErrorKind::NameNotGenerated(..) => "NameNotGenerated",
+        }
+    }
+}
+
+impl fmt::Display for FieldErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldErrorKind::ParseError(field) => write!(
+                f,
+                "Failed to parse '{}'. Please ensure the value is in the correct format", 
+                field
+            ),
+            FieldErrorKind::RequiredFieldEmpty(field) => write!(
+                f,
+                "The '{}' field cannot be empty. Please provide a value", 
+                field
+            ),
+            FieldErrorKind::InvalidFieldName(name) => write!(
+                f,
+                "Field name '{}' is invalid. Names must contain only letters and numbers", 
+                name
+            ),
+            FieldErrorKind::InvalidLabel(label) => write!(
+                f,
+                "Invalid label: {}. Labels must contain visible characters", 
+                label
+            ),
+            FieldErrorKind::InvalidTypeId(type_id, suggestion) => {
+                write!(f, "Invalid type_id: {}.", type_id)?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " Did you mean '{}'?", suggestion.display_name())?;
+                }
+                write!(f, "\n\nValid values are (id or name):")?;
+                for field_type in FieldType::ALL {
+                    write!(f, "\n{} ({})", field_type.id(), field_type.display_name())?;
+                }
+                Ok(())
+            },
+            FieldErrorKind::InvalidInputType(field_type, suggestion) => {
+                if let Some(suggestion) = suggestion {
+                    write!(f, "Did you mean '{}'? ", suggestion.display_name())?;
+                }
+
+                if let [InputType::Default] = field_type.input_types() {
+                    return write!(
+                        f,
+                        "Field type {} only accepts input_type_id 0. This field type has no input options.",
+                        field_type.display_name()
+                    );
+                }
+
+                write!(
+                    f,
+                    "{} fields (type_id: {}) accept these input types (id or name):",
+                    field_type.display_name(),
+                    field_type.id()
+                )?;
+                for input_type in field_type.input_types() {
+                    write!(f, "\n{}: {}", input_type.id(), input_type.display_name())?;
+                }
+                Ok(())
+            },
+            FieldErrorKind::MissingOptions(msg) => write!(
+                f,
+                "{}. Please provide a comma-separated list of options", 
+                msg
+            ),
+            FieldErrorKind::InvalidFlag(column) => write!(
+                f,
+                "'{}' must be true or false (yes/no, y/n and 1/0 are accepted too). Leave it empty to keep the default (true)",
+                column
+            ),
+            FieldErrorKind::InvalidOptionOrder(order) => write!(
+                f,
+                "Invalid option_order '{}'. Use 'written' to keep the order of the file or 'alphabetical'",
+                order
+            ),
+            FieldErrorKind::DuplicateOptions(options) => write!(
+                f,
+                "Options must be unique (ignoring case), found more than once: {}",
+                options.join(", ")
+            ),
+            FieldErrorKind::DuplicateName(first) => write!(
+                f,
+                "This name is already used in {}. Every field needs its own name",
+                first.in_sentence()
+            ),
+            FieldErrorKind::NameCollision(name, first) => write!(
+                f,
+                "This name only differs in case from '{}' in {}. Halo compares names without case, so the second field would fail",
+                name,
+                first.in_sentence()
+            ),
+            FieldErrorKind::DuplicateLabel(first) => write!(
+                f,
+                "This label is already used in {} (ignoring case). Fields with the same label can't be told apart in Halo",
+                first.in_sentence()
+            ),
+            FieldErrorKind::InvalidUsage(usage, suggestion) => {
+                write!(f, "Invalid usage: {}.", usage)?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " Did you mean '{}'?", suggestion.display_name())?;
+                }
+                write!(f, "\n\nValid values are (id or name):")?;
+                for entity in Entity::ALL {
+                    write!(f, "\n{} ({})", entity.id(), entity.display_name())?;
+                }
+                Ok(())
+            },
+            FieldErrorKind::FieldTypeNotAllowed(field_type, usage) => {
+                let allowed: Vec<_> = Entity::ALL.iter()
+                    .filter(|entity| entity.allows(*field_type))
+                    .map(|entity| entity.display_name())
+                    .collect();
+                write!(
+                    f,
+                    "{} fields cannot be added to {}. They are only available on: {}",
+                    field_type.display_name(),
+                    usage.display_name(),
+                    allowed.join(", ")
+                )
+            },
+            FieldErrorKind::LintViolation(rule, message) => write!(f, "{} (lint rule '{}')", message, rule),
+            FieldErrorKind::NameNotGenerated(label, reason) => write!(
+                f,
+                "No name can be generated from the label '{}', {}. Please fill in the name",
+                label,
+                reason
+            ),
+        }
+    }
+}
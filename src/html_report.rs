@@ -0,0 +1,179 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use chrono::Local;
+use crate::config::Config;
+use crate::error::{Result, CustomError, IOErrorKind};
+use crate::logging;
+use crate::models::field::Field;
+use crate::models::import_result::{ImportResults, xml_escape};
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 2rem; color: #1f2933; }
+h1 { font-size: 1.5rem; margin-bottom: 0.25rem; }
+.subtitle { color: #616e7c; margin-top: 0; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+th, td { border-bottom: 1px solid #e4e7eb; padding: 0.4rem 0.6rem; text-align: left; vertical-align: top; }
+.meta th { width: 12rem; color: #616e7c; font-weight: normal; }
+.results th { background: #f5f7fa; cursor: pointer; user-select: none; white-space: nowrap; }
+.results th::after { content: " \2195"; color: #9aa5b1; }
+.status { font-weight: 600; text-transform: capitalize; }
+.succeeded { color: #2f8132; }
+.failed { color: #c62828; }
+.skipped { color: #0b69a3; }
+.filters button { border: 1px solid #cbd2d9; background: #fff; padding: 0.3rem 0.8rem; margin-right: 0.3rem; border-radius: 4px; cursor: pointer; }
+.filters button.active { background: #1f2933; color: #fff; }
+pre { margin: 0.4rem 0 0; background: #f5f7fa; padding: 0.5rem; font-size: 0.8rem; max-width: 40rem; overflow-x: auto; }
+"#;
+
+// Sorts by the clicked column (numbers numerically) and hides rows not matching the status filter
+const SCRIPT: &str = r#"
+document.querySelectorAll('.results th').forEach(function (header, column) {
+  header.addEventListener('click', function () {
+    var body = document.querySelector('.results tbody');
+    var ascending = header.dataset.order !== 'asc';
+    document.querySelectorAll('.results th').forEach(function (other) { delete other.dataset.order; });
+    header.dataset.order = ascending ? 'asc' : 'desc';
+    var value = function (row) { var cell = row.children[column]; return cell.dataset.sort !== undefined ? cell.dataset.sort : cell.textContent.trim(); };
+    Array.from(body.rows)
+      .sort(function (a, b) {
+        var x = value(a), y = value(b);
+        var result = (x !== '' && y !== '' && !isNaN(x) && !isNaN(y)) ? x - y : x.localeCompare(y);
+        return ascending ? result : -result;
+      })
+      .forEach(function (row) { body.appendChild(row); });
+  });
+});
+document.querySelectorAll('.filters button').forEach(function (button) {
+  button.addEventListener('click', function () {
+    document.querySelectorAll('.filters button').forEach(function (other) { other.classList.remove('active'); });
+    button.classList.add('active');
+    document.querySelectorAll('.results tbody tr').forEach(function (row) {
+      row.hidden = button.dataset.status !== 'all' && row.dataset.status !== button.dataset.status;
+    });
+  });
+});
+"#;
+
+/// A standalone HTML page describing what a run changed on the instance, written to
+/// the logs directory next to the run's log file.
+pub struct HtmlReport<'a> {
+    title: &'a str,
+    config: &'a Config,
+    fields: &'a [Field],
+    results: &'a ImportResults,
+}
+
+fn format_duration(ms: u128) -> String {
+    match ms {
+        0..=999 => format!("{} ms", ms),
+        1000..=59_999 => format!("{:.1} s", ms as f64 / 1000.0),
+        _ => format!("{} min {} s", ms / 60_000, ms % 60_000 / 1000),
+    }
+}
+
+// The account running the program, `USERNAME` on Windows and `USER` elsewhere
+fn operator() -> String {
+    env::var("USERNAME")
+        .or_else(|_| env::var("USER"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+impl<'a> HtmlReport<'a> {
+    pub fn new(title: &'a str, config: &'a Config, fields: &'a [Field], results: &'a ImportResults) -> Self {
+        HtmlReport { title, config, fields, results }
+    }
+
+    pub fn write(&self) -> Result<PathBuf> {
+        let path = logging::session_file("html");
+        fs::write(&path, self.render()).map_err(|e|
+            CustomError::IOError(IOErrorKind::WriteFile(format!("{}: {}", path.display(), e)))
+        )?;
+        Ok(path)
+    }
+
+    fn render(&self) -> String {
+        let started = logging::session_started();
+        let finished = Local::now();
+        let results = self.results.in_order();
+        let api_time: u128 = results.iter().map(|(_, result)| result.duration_ms).sum();
+
+        let mut meta = vec![
+            ("Run", self.title.to_string()),
+            ("Instance", self.config.base_url.clone()),
+            ("Tenant", if self.config.tenant.is_empty() { "-".to_string() } else { self.config.tenant.clone() }),
+            ("Operator", operator()),
+        ];
+        if !self.config.source_file_name.is_empty() {
+            meta.push(("Input file", self.config.source_file_name.clone()));
+        }
+        meta.extend([
+            ("Started", started.format("%Y-%m-%d %H:%M:%S").to_string()),
+            ("Finished", finished.format("%Y-%m-%d %H:%M:%S").to_string()),
+            ("Duration", format_duration((finished - started).num_milliseconds().max(0) as u128)),
+            ("Time spent on API calls", format_duration(api_time)),
+            ("Results", format!(
+                "{} successful, {} failed, {} skipped",
+                self.results.successful.len(),
+                self.results.failed.len(),
+                self.results.skipped.len()
+            )),
+        ]);
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{} report - {}</title>\n", xml_escape(self.title), started.format("%Y-%m-%d %H:%M")));
+        html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+        html.push_str(&format!("<h1>{} report</h1>\n", xml_escape(self.title)));
+        html.push_str(&format!("<p class=\"subtitle\">{}</p>\n", xml_escape(&self.config.base_url)));
+
+        html.push_str("<table class=\"meta\">\n");
+        for (name, value) in meta {
+            html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", name, xml_escape(&value)));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<div class=\"filters\">\n");
+        html.push_str(&format!("<button class=\"active\" data-status=\"all\">All ({})</button>\n", results.len()));
+        for (status, count) in [
+            ("succeeded", self.results.successful.len()),
+            ("failed", self.results.failed.len()),
+            ("skipped", self.results.skipped.len()),
+        ] {
+            html.push_str(&format!("<button data-status=\"{0}\">{0} ({1})</button>\n", status, count));
+        }
+        html.push_str("</div>\n");
+
+        html.push_str("<table class=\"results\">\n<thead><tr>");
+        for header in ["#", "Status", "Label", "Name", "Halo id", "Usage", "Attempts", "Duration", "Message", "Time", "Payload"] {
+            html.push_str(&format!("<th>{}</th>", header));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+
+        for (index, (status, result)) in results.iter().enumerate() {
+            let field = self.fields.iter().find(|field| field.label == result.label);
+            // Only requests that were sent have a payload, skipped fields and deletions don't
+            let payload = result.payload.as_deref()
+                .map(|json| format!("<details><summary>Show</summary><pre>{}</pre></details>", xml_escape(json)))
+                .unwrap_or_default();
+
+            html.push_str(&format!("<tr data-status=\"{}\">", status));
+            html.push_str(&format!("<td>{}</td>", index + 1));
+            html.push_str(&format!("<td class=\"status {0}\">{0}</td>", status));
+            html.push_str(&format!("<td>{}</td>", xml_escape(&result.label)));
+            html.push_str(&format!("<td>{}</td>", xml_escape(field.map(|field| field.name.as_str()).unwrap_or_default())));
+            html.push_str(&format!("<td>{}</td>", result.halo_id.map(|id| id.to_string()).unwrap_or_default()));
+            html.push_str(&format!("<td>{}</td>", result.usage.map(|usage| usage.display_name()).unwrap_or("Other")));
+            html.push_str(&format!("<td>{}</td>", result.attempts));
+            html.push_str(&format!("<td data-sort=\"{}\">{}</td>", result.duration_ms, format_duration(result.duration_ms)));
+            html.push_str(&format!("<td>{}</td>", xml_escape(result.error.as_deref().unwrap_or_default())));
+            html.push_str(&format!("<td>{}</td>", result.timestamp.format("%H:%M:%S")));
+            html.push_str(&format!("<td>{}</td>", payload));
+            html.push_str("</tr>\n");
+        }
+
+        html.push_str("</tbody>\n</table>\n");
+        html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+        html
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::fs;
+use serde::{Serialize, Deserialize};
+use crate::error::{Result, CustomError, ConfigErrorKind};
+use crate::models::halo_field::HALO_NAME_PREFIX;
+
+// Small words title case leaves in lower case unless they start the label
+const MINOR_WORDS: [&str; 12] = ["a", "an", "and", "as", "at", "by", "for", "in", "of", "on", "or", "the"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameCase {
+    Pascal,
+    Camel,
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelCase {
+    Title,
+    Sentence,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PrefixRule {
+    pub value: String,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NameCaseRule {
+    pub style: NameCase,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LengthRule {
+    pub value: usize,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReservedNamesRule {
+    pub values: Vec<String>,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LabelCaseRule {
+    pub style: LabelCase,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PunctuationRule {
+    // Characters a label may still end with, e.g. `?` for yes/no questions
+    #[serde(default)]
+    pub allow: Vec<char>,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// Team naming policy for field names and labels, read from `LINT_RULES_FILE`.
+/// Every rule is optional, a missing rule is not checked.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintRules {
+    pub name_prefix: Option<PrefixRule>,
+    pub name_case: Option<NameCaseRule>,
+    pub name_max_length: Option<LengthRule>,
+    pub reserved_names: Option<ReservedNamesRule>,
+    pub label_case: Option<LabelCaseRule>,
+    pub label_trailing_punctuation: Option<PunctuationRule>,
+}
+
+/// A rule a field broke, reported against the column it concerns.
+#[derive(Debug)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub column: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl LintRules {
+    /// Reads the rules from a YAML file, JSON works as well since YAML is a superset of it.
+    pub fn load(path: &str) -> Result<Self> {
+        let invalid = |message: String| CustomError::ConfigError(ConfigErrorKind::InvalidLintRules(path.to_string(), message));
+
+        let text = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        if text.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let rules: Self = serde_yaml::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+
+        // Names are written without Halo's prefix, which it adds again on creation
+        if let Some(rule) = rules.name_prefix.as_ref().filter(|rule| rule.value.starts_with(HALO_NAME_PREFIX)) {
+            return Err(invalid(format!(
+                "name_prefix '{}' starts with '{}', which Halo adds to every name itself", rule.value, HALO_NAME_PREFIX
+            )));
+        }
+        Ok(rules)
+    }
+
+    pub fn check(&self, name: &str, label: &str) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        let mut report = |rule, column, severity, message| {
+            findings.push(LintFinding { rule, column, severity, message });
+        };
+
+        // Case is checked on the part after the prefix, `AcmeRegion` is PascalCase
+        let mut base_name = name;
+        if let Some(rule) = &self.name_prefix {
+            match name.strip_prefix(rule.value.as_str()) {
+                Some(stripped) => base_name = stripped,
+                None => report("name_prefix", "name", rule.severity, format!("Names must start with '{}'", rule.value)),
+            }
+        }
+
+        if let Some(rule) = &self.name_case {
+            if !Self::has_name_case(base_name, rule.style) {
+                report("name_case", "name", rule.severity, format!("Names must be written in {}", rule.style));
+            }
+        }
+
+        if let Some(rule) = &self.name_max_length {
+            let length = name.chars().count();
+            if length > rule.value {
+                report("name_max_length", "name", rule.severity, format!(
+                    "Names can be at most {} characters long, this one has {}", rule.value, length
+                ));
+            }
+        }
+
+        if let Some(rule) = &self.reserved_names {
+            let reserved = rule.values.iter()
+                .find(|reserved| reserved.eq_ignore_ascii_case(name) || reserved.eq_ignore_ascii_case(base_name));
+            if let Some(reserved) = reserved {
+                report("reserved_names", "name", rule.severity, format!("'{}' is a reserved name", reserved));
+            }
+        }
+
+        if let Some(rule) = &self.label_case {
+            if !Self::has_label_case(label, rule.style) {
+                report("label_case", "label", rule.severity, format!("Labels must be written in {}", rule.style));
+            }
+        }
+
+        if let Some(rule) = &self.label_trailing_punctuation {
+            if let Some(last) = label.chars().last().filter(|c| c.is_ascii_punctuation() && !rule.allow.contains(c)) {
+                report("label_trailing_punctuation", "label", rule.severity, format!("Labels must not end with '{}'", last));
+            }
+        }
+
+        findings
+    }
+
+    fn has_name_case(name: &str, style: NameCase) -> bool {
+        let Some(first) = name.chars().next() else {
+            return true;
+        };
+
+        match style {
+            NameCase::Pascal => first.is_uppercase(),
+            NameCase::Camel => first.is_lowercase(),
+            NameCase::Upper => !name.chars().any(char::is_lowercase),
+            NameCase::Lower => !name.chars().any(char::is_uppercase),
+        }
+    }
+
+    fn has_label_case(label: &str, style: LabelCase) -> bool {
+        let starts_upper = |word: &str| word.chars().next().is_none_or(|c| !c.is_lowercase());
+
+        match style {
+            LabelCase::Sentence => starts_upper(label),
+            LabelCase::Title => label.split_whitespace()
+                .enumerate()
+                .all(|(index, word)| starts_upper(word) || (index > 0 && MINOR_WORDS.contains(&word))),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+impl fmt::Display for NameCase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NameCase::Pascal => write!(f, "PascalCase"),
+            NameCase::Camel => write!(f, "camelCase"),
+            NameCase::Upper => write!(f, "UPPERCASE"),
+            NameCase::Lower => write!(f, "lowercase"),
+        }
+    }
+}
+
+impl fmt::Display for LabelCase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LabelCase::Title => write!(f, "Title Case"),
+            LabelCase::Sentence => write!(f, "Sentence case"),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::fs;
 use std::fs::OpenOptions;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use log::{LevelFilter, info, error};
 use simplelog::*;
 use chrono::{Local, Duration, DateTime};
@@ -10,7 +11,23 @@ use crate::models::import_result::ImportResults;
 const MAX_LOG_DAYS: i64 = 7;
 const MAX_LOGS: usize = 100;
 
-pub fn setup_logging() -> Result<()> {
+// Start of the session and its log file, so reports can be written next to the log
+static SESSION: OnceLock<(DateTime<Local>, PathBuf)> = OnceLock::new();
+
+/// When the current session started, or now when logging was never set up.
+pub fn session_started() -> DateTime<Local> {
+    SESSION.get().map_or_else(Local::now, |(started, _)| *started)
+}
+
+/// Path of a file that belongs to the current session's log, e.g. `logs/run_<timestamp>.html`.
+pub fn session_file(extension: &str) -> PathBuf {
+    match SESSION.get() {
+        Some((_, log_file)) => log_file.with_extension(extension),
+        None => Path::new("logs").join(format!("run_{}.{}", Local::now().format("%Y-%m-%d_%H-%M-%S"), extension)),
+    }
+}
+
+pub fn setup_logging(level: LevelFilter) -> Result<()> {
     // Create logs directory if it doesn't exist
     let logs_dir = Path::new("logs");
     fs::create_dir_all(logs_dir).map_err(|e| 
@@ -20,8 +37,10 @@ pub fn setup_logging() -> Result<()> {
     // Cleanup old logs
     cleanup_old_logs(logs_dir)?;
 
-    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let started = Local::now();
+    let timestamp = started.format("%Y-%m-%d_%H-%M-%S");
     let log_file = logs_dir.join(format!("run_{}.log", timestamp));
+    let _ = SESSION.set((started, log_file.clone()));
     
     // Configure file logger with timestamps
     let file_config = ConfigBuilder::new()
@@ -42,17 +61,16 @@ pub fn setup_logging() -> Result<()> {
 
     CombinedLogger::init(vec![
         TermLogger::new(
-            LevelFilter::Info,
+            level,
             term_config,
             TerminalMode::Mixed,
             ColorChoice::Auto,
         ),
         WriteLogger::new(
-            LevelFilter::Info,
+            level,
             file_config,
             OpenOptions::new()
                 .create(true)
-                .write(true)
                 .append(true)
                 .open(&log_file)
                 .map_err(|e| CustomError::IOError(IOErrorKind::WriteFile(e.to_string())))?,
@@ -73,7 +91,7 @@ fn cleanup_old_logs(logs_dir: &Path) -> Result<()> {
         .filter(|entry| {
             entry.path()
                 .extension()
-                .map_or(false, |ext| ext == "log")
+                .is_some_and(|ext| ext == "log")
         })
         .collect();
 
@@ -86,13 +104,14 @@ fn cleanup_old_logs(logs_dir: &Path) -> Result<()> {
 
     let cutoff_date = Local::now() - Duration::days(MAX_LOG_DAYS);
 
-    // Remove old files
+    // Remove old files, together with the HTML report of the same run
     for entry in log_files.iter().skip(MAX_LOGS) {
         if let Ok(metadata) = entry.metadata() {
             if let Ok(modified) = metadata.modified() {
                 let modified: DateTime<Local> = modified.into();
                 if modified < cutoff_date {
                     let _ = fs::remove_file(entry.path());
+                    let _ = fs::remove_file(entry.path().with_extension("html"));
                 }
             }
         }
@@ -114,7 +133,7 @@ pub fn get_log_stats() -> Result<String> {
             .map_err(|e| CustomError::IOError(IOErrorKind::ReadDir(e.to_string())))? {
             let entry = entry.map_err(|e| CustomError::IOError(IOErrorKind::ReadFile(e.to_string())))?;
             
-            if entry.path().extension().map_or(false, |ext| ext == "log") {
+            if entry.path().extension().is_some_and(|ext| ext == "log") {
                 file_count += 1;
                 let metadata = entry.metadata()
                     .map_err(|e| CustomError::IOError(IOErrorKind::Metadata(e.to_string())))?;
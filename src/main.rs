@@ -7,26 +7,143 @@ mod logging;
 mod screens;
 mod api;
 mod transformers;
+mod sync;
+mod writers;
+mod cli;
+mod html_report;
+mod lint;
+mod naming;
 
 use config::Config;
-use readers::CsvReader;
-use error::{Result, CustomError, AuthErrorKind};
+use readers::FieldReader;
+use error::{Result, CustomError, AuthErrorKind, IOErrorKind};
 use auth::client::AuthClient;
-use log::{info, error};
+use log::{info, warn, error};
 use screens::{ScreenManager, RunMode};
 use api::field_client::FieldClient;
+use api::rate_limit::RateLimiter;
+use models::field::Field;
+use models::halo_field::HaloField;
+use models::run_journal::RunJournal;
+use models::import_result::{ImportResults, ReportFormat};
+use std::fs;
+use std::path::Path;
+use std::io::IsTerminal;
+use std::sync::Arc;
+use sync::SyncPlan;
+use writers::CsvWriter;
+use clap::{CommandFactory, Parser};
+use cli::{Cli, Command};
+use html_report::HtmlReport;
 
-async fn run() -> Result<()> {
-    logging::setup_logging()?;
+async fn run(cli: Cli) -> Result<()> {
+    logging::setup_logging(cli.log_level)?;
 
     info!("Starting application...\n");
     
     info!("Loading configuration...");
-    let config = Config::new()?;
+    let mut config = Config::new(cli.env_file.as_deref())?;
+    if let Some(file) = &cli.file {
+        config.source_file_name = file.clone();
+    }
+    if let Some(sheet) = &cli.sheet {
+        config.source_sheet = Some(sheet.clone());
+    }
+    if let Some(usage) = cli.default_usage {
+        config.default_usage = usage;
+    }
+    if let Some(path) = &cli.validation_report {
+        config.validation_report_file = Some(path.clone());
+    }
+    if let Some(path) = &cli.report {
+        config.import_report_file = Some(path.clone());
+    }
+    if let Some(path) = &cli.lint_rules {
+        config.lint_rules_file = Some(path.clone());
+    }
+    match &cli.command {
+        Some(Command::Export { output: Some(output) }) => config.export_file_name = output.clone(),
+        Some(Command::RetryFailed { failed_file: Some(path) }) => config.failed_file_name = path.clone(),
+        Some(Command::Clone { source_env_file: Some(path), .. }) => config.clone_source_env_file = path.clone(),
+        Some(Command::Apply { delete_orphans: true }) => config.sync_delete_orphans = true,
+        _ => {}
+    }
+    // A mistyped report extension is reported before anything is imported
+    if let Some(path) = &config.import_report_file {
+        ReportFormat::from_path(path)?;
+    }
     info!("✓ Configuration loaded successfully\n");
+
+    // A resumed run reads the same input file as the run it continues
+    let resumed = match cli.command.as_ref().and_then(Command::resume) {
+        Some(run_id) => {
+            let journal = RunJournal::load(run_id)?;
+            journal.verify_source()?;
+            config.source_file_name = journal.source_file.clone();
+            config.source_sheet = journal.source_sheet.clone();
+            info!("Resuming run {}: {} of {} fields still pending or failed\n",
+                journal.run_id,
+                journal.open_count(),
+                journal.entries.len()
+            );
+            Some(journal)
+        },
+        None => None,
+    };
+
+    // Validation never needs to talk to Halo
+    if let Some(Command::Validate) = cli.command {
+        info!("Reading input file...");
+        let fields = FieldReader::new().read_fields(&config)?;
+        info!("✓ Successfully validated {} fields", fields.len());
+        return Ok(());
+    }
+
+    // Neither does a dry run, it only renders what would be sent
+    if let Some(Command::Import { dry_run: true, dry_run_output, .. }) = &cli.command {
+        return dry_run(&config, dry_run_output.as_deref());
+    }
+
+    let run_mode = match &cli.command {
+        Some(command) => command.run_mode(),
+        None => ScreenManager::get_run_mode()?,
+    };
+    if run_mode == RunMode::Quit {
+        info!("Program terminated by user");
+        return Ok(());
+    }
+    // Debug mode asks about every field, which needs someone at a terminal
+    if run_mode == RunMode::Debug && !std::io::stdin().is_terminal() {
+        return Err(CustomError::NotInteractive(
+            "debug mode prompts for every field, use import when stdin is not a terminal".to_string()
+        ));
+    }
+
+    // A retry imports the failed fields file instead of the input file
+    if run_mode == RunMode::RetryFailed {
+        if !Path::new(&config.failed_file_name).exists() {
+            info!("No failed fields to retry, {} does not exist", config.failed_file_name);
+            return Ok(());
+        }
+        ScreenManager::prompt_failed_fixes(&config.failed_file_name, cli.yes)?;
+        config.source_file_name = config.failed_file_name.clone();
+        config.source_sheet = None;
+    }
+
+    // Mistakes in the input file are reported before logging in to Halo
+    let fields = match run_mode {
+        RunMode::Export | RunMode::Clone => None,
+        _ => {
+            info!("Reading input file...");
+            let fields = FieldReader::new().read_fields(&config)?;
+            info!("✓ Successfully validated {} fields\n", fields.len());
+            Some(fields)
+        },
+    };
     
     info!("Authenticating with API...");
-    let auth_client = AuthClient::new(config.clone());
+    let rate_limiter = Arc::new(RateLimiter::from_config(&config));
+    let auth_client = Arc::new(AuthClient::new(config.clone(), Arc::clone(&rate_limiter)));
     
     let token = match auth_client.get_valid_token().await {
         Ok(token) => {
@@ -45,44 +162,275 @@ async fn run() -> Result<()> {
         }
     };
     
-    info!("Reading CSV file...");
-    let reader = CsvReader::new();
-    let fields = reader.read_fields(&config)?;
-    info!("✓ Successfully validated {} fields\n", fields.len());
-    
-    let screen_manager = ScreenManager::new(fields);
-    screen_manager.show_initial_stats(&token.split_whitespace().next().unwrap_or("Unknown"))?;
+    let field_client = FieldClient::new(config.clone(), auth_client, rate_limiter);
+
+    info!("Fetching existing fields from Halo...");
+    let existing_fields = field_client.list_fields().await?;
+    info!("✓ Found {} existing fields\n", existing_fields.len());
 
-    let field_client = FieldClient::new(config.clone(), token);
+    let mut fields = match (run_mode, fields) {
+        (RunMode::Export, _) => return export_fields(&config, &existing_fields),
+        (RunMode::Clone, _) => {
+            let pattern = match &cli.command {
+                Some(Command::Clone { pattern, .. }) => pattern.clone(),
+                _ => None,
+            };
+            return clone_fields(&config, &field_client, &existing_fields, pattern, cli.yes).await;
+        },
+        (_, Some(fields)) => fields,
+        (_, None) => unreachable!("the input file is read for every other mode"),
+    };
+    if let Some(journal) = &resumed {
+        fields.retain(|field| journal.is_open(&field.label));
+    }
+
+    let mut screen_manager = ScreenManager::new(fields, cli.yes);
+    screen_manager.show_initial_stats(token.split_whitespace().next().unwrap_or("Unknown"))?;
+    // The sync plan does its own comparison with the instance
+    if !matches!(run_mode, RunMode::Plan | RunMode::Apply) {
+        screen_manager.preflight(&existing_fields);
+        screen_manager.show_preflight_summary();
+    }
     
-    match screen_manager.get_run_mode()? {
-        RunMode::Import => {
+    match run_mode {
+        RunMode::Import | RunMode::RetryFailed => {
             info!("\n{}", "=".repeat(80));
-            info!("Starting Full Import Mode");
+            if run_mode == RunMode::RetryFailed {
+                info!("Retrying Failed Fields");
+            } else {
+                info!("Starting Full Import Mode");
+            }
             info!("{}\n", "=".repeat(80));
             
-            let results = screen_manager.process_all_fields(&field_client).await?;
+            if !screen_manager.confirm("Proceed with the import?")? {
+                info!("Import cancelled by user");
+                return Ok(());
+            }
+
+            let journal = start_journal(&config, resumed, screen_manager.fields())?;
+            let results = screen_manager.process_all_fields(&field_client, Some(journal)).await?;
             results.log_summary();
+            write_reports(&config, run_mode, screen_manager.fields(), &results)?;
+            save_failed(&config, screen_manager.fields(), &results)?;
         },
         RunMode::Debug => {
             info!("\n{}", "=".repeat(80));
             info!("Starting Debug Mode");
             info!("{}\n", "=".repeat(80));
             
-            let results = screen_manager.debug_mode(&field_client).await?;
+            let journal = start_journal(&config, resumed, screen_manager.fields())?;
+            let results = screen_manager.debug_mode(&field_client, Some(journal)).await?;
             results.log_summary();
+            write_reports(&config, run_mode, screen_manager.fields(), &results)?;
+            save_failed(&config, screen_manager.fields(), &results)?;
         },
-        RunMode::Quit => {
-            info!("Program terminated by user");
-        }
+        RunMode::Plan => {
+            let plan = SyncPlan::build(screen_manager.fields(), &existing_fields, config.sync_delete_orphans);
+            plan.print();
+        },
+        RunMode::Apply => {
+            info!("\n{}", "=".repeat(80));
+            info!("Starting Sync Apply Mode");
+            info!("{}\n", "=".repeat(80));
+
+            let mut plan = SyncPlan::build(screen_manager.fields(), &existing_fields, config.sync_delete_orphans);
+            plan.print();
+
+            if !plan.has_changes() {
+                info!("Instance is already in sync, nothing to apply");
+            } else if screen_manager.confirm("Apply this plan?")? {
+                let deletions = plan.deletions();
+                let delete_orphans = matches!(cli.command, Some(Command::Apply { delete_orphans: true }));
+                if !deletions.is_empty() && !screen_manager.confirm_deletions(&deletions, delete_orphans)? {
+                    plan.keep_orphans();
+                }
+
+                let results = plan.apply(&field_client).await;
+                results.log_summary();
+                write_reports(&config, run_mode, screen_manager.fields(), &results)?;
+            } else {
+                info!("Sync apply cancelled by user");
+            }
+        },
+        RunMode::Validate | RunMode::Export | RunMode::Clone | RunMode::Quit => {
+            unreachable!("handled before the import screens")
+        },
+    }
+
+    Ok(())
+}
+
+// Continues the resumed journal, or records a new run of the given fields
+fn start_journal(config: &Config, resumed: Option<RunJournal>, fields: &[Field]) -> Result<RunJournal> {
+    let journal = match resumed {
+        Some(journal) => journal,
+        None => RunJournal::start(config, fields)?,
+    };
+    info!("Run id: {} (journal {})\n", journal.run_id, journal.path().display());
+    Ok(journal)
+}
+
+// The HTML report is always written to the logs directory, the machine-readable
+// report (e.g. for CI systems) only when configured
+fn write_reports(config: &Config, run_mode: RunMode, fields: &[Field], results: &ImportResults) -> Result<()> {
+    if let Some(path) = &config.import_report_file {
+        results.write_report(path)?;
+        info!("✓ Import report written to {}", path);
+    }
+
+    let path = HtmlReport::new(run_mode.title(), config, fields, results).write()?;
+    info!("✓ HTML report written to {}", path.display());
+    Ok(())
+}
+
+// Keeps the definitions of failed fields so they can be fixed and sent again with `retry-failed`
+fn save_failed(config: &Config, fields: &[Field], results: &ImportResults) -> Result<()> {
+    let failed: Vec<_> = results.failed.iter()
+        .filter_map(|result| fields.iter()
+            .find(|field| field.label == result.label)
+            .map(|field| (field, result.error.as_deref().unwrap_or_default())))
+        .collect();
+
+    if !failed.is_empty() {
+        CsvWriter::new().write_failed(&config.failed_file_name, &failed)?;
+        info!("✓ Saved {} failed field(s) to {}, send them again with retry-failed",
+            failed.len(),
+            config.failed_file_name
+        );
+    } else if Path::new(&config.failed_file_name).exists() {
+        // Nothing is left to retry, a file from an earlier run would only be sent again next time
+        fs::remove_file(&config.failed_file_name).map_err(|e|
+            CustomError::IOError(IOErrorKind::WriteFile(format!("{}: {}", config.failed_file_name, e)))
+        )?;
+        info!("✓ No fields failed, removed {} from an earlier run", config.failed_file_name);
+    }
+
+    Ok(())
+}
+
+fn dry_run(config: &Config, output: Option<&str>) -> Result<()> {
+    info!("\n{}", "=".repeat(80));
+    info!("Starting Dry Run (nothing will be sent)");
+    info!("{}\n", "=".repeat(80));
+
+    info!("Reading input file...");
+    let fields = FieldReader::new().read_fields(config)?;
+    info!("✓ Successfully validated {} fields\n", fields.len());
+
+    let mut rendered = String::new();
+    for (index, field) in fields.iter().enumerate() {
+        let request = FieldClient::render_create_request(config, field)?;
+        rendered.push_str(&format!("### Field {} of {}: {}\n{}\n", index + 1, fields.len(), field.label, request));
+    }
+
+    print!("{}", rendered);
+
+    if let Some(path) = output {
+        fs::write(path, &rendered).map_err(|e|
+            CustomError::IOError(IOErrorKind::WriteFile(format!("{}: {}", path, e)))
+        )?;
+        info!("✓ Wrote {} requests to {}", fields.len(), path);
     }
 
     Ok(())
 }
 
+fn export_fields(config: &Config, existing_fields: &[HaloField]) -> Result<()> {
+    info!("\n{}", "=".repeat(80));
+    info!("Starting Export Mode");
+    info!("{}\n", "=".repeat(80));
+
+    let fields: Vec<_> = existing_fields.iter()
+        .filter_map(|halo_field| match halo_field.to_field() {
+            Ok(field) => Some(field),
+            Err(reason) => {
+                warn!("Skipping field '{}': {}", halo_field.name, reason);
+                None
+            },
+        })
+        .collect();
+
+    CsvWriter::new().write_fields(&config.export_file_name, &fields)?;
+    info!("✓ Exported {} of {} fields to {}", 
+        fields.len(), 
+        existing_fields.len(), 
+        config.export_file_name
+    );
+
+    Ok(())
+}
+
+async fn clone_fields(
+    config: &Config,
+    target_client: &FieldClient,
+    target_fields: &[HaloField],
+    pattern: Option<String>,
+    assume_yes: bool,
+) -> Result<()> {
+    info!("\n{}", "=".repeat(80));
+    info!("Starting Clone Mode");
+    info!("{}\n", "=".repeat(80));
+
+    info!("Loading source profile from {}...", config.clone_source_env_file);
+    let source_config = Config::from_env_file(&config.clone_source_env_file)?;
+    info!("✓ Source instance: {}\n", source_config.base_url);
+
+    // Separate instances have separate rate limit budgets
+    let source_rate_limiter = Arc::new(RateLimiter::from_config(&source_config));
+    let source_auth = Arc::new(AuthClient::new(source_config.clone(), Arc::clone(&source_rate_limiter)));
+    source_auth.get_valid_token().await?;
+    info!("✓ Authenticated with source instance");
+
+    let source_client = FieldClient::new(source_config, source_auth, source_rate_limiter);
+    let source_fields = source_client.list_fields().await?;
+    info!("✓ Found {} fields on source instance", source_fields.len());
+
+    // Only ask for a pattern when someone is there to answer
+    let pattern = match pattern {
+        Some(pattern) => pattern,
+        None if assume_yes || !std::io::stdin().is_terminal() => String::new(),
+        None => ScreenManager::get_name_pattern()?,
+    };
+    let fields: Vec<_> = source_fields.iter()
+        .filter(|halo_field| halo_field.matches_pattern(&pattern))
+        .filter_map(|halo_field| match halo_field.to_field() {
+            Ok(field) => Some(field),
+            Err(reason) => {
+                warn!("Skipping field '{}', it cannot be cloned: {}", halo_field.name, reason);
+                None
+            },
+        })
+        .collect();
+    info!("✓ {} fields match the pattern\n", fields.len());
+
+    let mut screen_manager = ScreenManager::new(fields, assume_yes);
+    screen_manager.preflight(target_fields);
+    screen_manager.show_preflight_summary();
+
+    if !screen_manager.confirm("Clone these fields to the target instance?")? {
+        info!("Clone cancelled by user");
+        return Ok(());
+    }
+
+    let results = screen_manager.process_all_fields(target_client, None).await?;
+    results.log_summary();
+    write_reports(config, RunMode::Clone, screen_manager.fields(), &results)?;
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    if let Err(e) = run().await {
+    let cli = Cli::parse();
+
+    // The interactive menu needs someone at a terminal to answer it
+    if cli.command.is_none() && !std::io::stdin().is_terminal() {
+        eprintln!("No subcommand given and stdin is not a terminal.\n");
+        eprintln!("{}", Cli::command().render_help());
+        std::process::exit(2);
+    }
+
+    if let Err(e) = run(cli).await {
         error!("\n✗ Error: {}", e);
         std::process::exit(1);
     }
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Serialize, Deserialize};
+use crate::models::field_type::{FieldType, closest, resolve};
+
+/// The Halo record type a custom field belongs to, sent as the field's `usage`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Entity {
+    #[default]
+    Ticket,
+    Action,
+    User,
+    Client,
+    Site,
+    Asset,
+    Contract,
+    Supplier,
+    Item,
+}
+
+impl Entity {
+    pub const ALL: [Entity; 9] = [
+        Entity::Ticket,
+        Entity::Action,
+        Entity::User,
+        Entity::Client,
+        Entity::Site,
+        Entity::Asset,
+        Entity::Contract,
+        Entity::Supplier,
+        Entity::Item,
+    ];
+
+    pub fn id(self) -> u8 {
+        match self {
+            Entity::Ticket => 1,
+            Entity::Action => 2,
+            Entity::User => 3,
+            Entity::Client => 4,
+            Entity::Site => 5,
+            Entity::Asset => 6,
+            Entity::Contract => 7,
+            Entity::Supplier => 8,
+            Entity::Item => 9,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|entity| entity.id() == id)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Entity::Ticket => "Tickets",
+            Entity::Action => "Actions",
+            Entity::User => "Users",
+            Entity::Client => "Clients",
+            Entity::Site => "Sites",
+            Entity::Asset => "Assets",
+            Entity::Contract => "Contracts",
+            Entity::Supplier => "Suppliers",
+            Entity::Item => "Items",
+        }
+    }
+
+    // Accepted spellings besides the display name, already normalized
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Entity::Ticket => &["ticket", "request", "requests"],
+            Entity::Action => &["action", "note", "notes"],
+            Entity::User => &["user", "enduser", "endusers"],
+            Entity::Client => &["client", "customer", "customers"],
+            Entity::Site => &["site", "location", "locations"],
+            Entity::Asset => &["asset", "device", "devices"],
+            Entity::Contract => &["contract"],
+            Entity::Supplier => &["supplier", "vendor", "vendors"],
+            Entity::Item => &["item", "product", "products"],
+        }
+    }
+
+    /// Rich text is only shown on ticket and action screens, every other type works everywhere.
+    pub fn allows(self, field_type: FieldType) -> bool {
+        field_type != FieldType::Rich || matches!(self, Entity::Ticket | Entity::Action)
+    }
+
+    /// The entity written in a `usage` column or `DEFAULT_USAGE`, see `resolve`.
+    pub fn parse(value: &str) -> Option<Self> {
+        resolve(value, Self::ALL.iter().map(|entity| (*entity, entity.id(), entity.display_name(), entity.aliases())))
+    }
+
+    /// The entity whose name is closest to a value that failed to parse.
+    pub fn suggest(value: &str) -> Option<Self> {
+        closest(value, Self::ALL.iter().map(|entity| (*entity, entity.display_name(), entity.aliases())))
+    }
+}
+
+// Lets the default entity be read from the configuration like any other value
+impl FromStr for Entity {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value).ok_or(())
+    }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.display_name(), self.id())
+    }
+}
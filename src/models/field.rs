@@ -1,27 +1,64 @@
 use serde::{Serialize, Deserialize};
+use crate::models::entity::Entity;
+use crate::models::field_type::{FieldType, InputType};
+use crate::models::option_list::OptionList;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
     pub name: String,
     pub label: String,
-    pub type_id: u8,
-    pub input_type_id: u8,
-    pub options: String,
+    pub field_type: FieldType,
+    pub input_type: InputType,
+    pub options: OptionList,
+    pub usage: Entity,
+    pub flags: FieldFlags,
+    // Set when the input file left the name blank and it was derived from the label
+    #[serde(default)]
+    pub generated_name: bool,
 }
+
+/// Search and copy-to-child behaviour of a field. Everything is enabled unless
+/// the input file switches it off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldFlags {
+    pub searchable: bool,
+    pub user_searchable: bool,
+    pub calendar_searchable: bool,
+    pub copy_to_child: bool,
+    pub copy_to_child_on_update: bool,
+}
+
+impl Default for FieldFlags {
+    fn default() -> Self {
+        FieldFlags {
+            searchable: true,
+            user_searchable: true,
+            calendar_searchable: true,
+            copy_to_child: true,
+            copy_to_child_on_update: true,
+        }
+    }
+}
+
 impl Field {
     pub fn new(
         name: String, 
         label: String, 
-        type_id: u8, 
-        input_type_id: u8, 
-        options: String
+        field_type: FieldType, 
+        input_type: InputType, 
+        options: OptionList,
+        usage: Entity,
+        flags: FieldFlags,
     ) -> Self {
         Field {
             name,
             label,
-            type_id,
-            input_type_id,
+            field_type,
+            input_type,
             options,
+            usage,
+            flags,
+            generated_name: false,
         }
     }
-}
\ No newline at end of file
+}
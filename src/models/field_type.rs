@@ -0,0 +1,282 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+
+// How similar a misspelled name must be to a known one before it is suggested
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+
+/// Halo custom field types supported by the input file, with their API ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldType {
+    Text,
+    Memo,
+    SingleSelection,
+    MultipleSelection,
+    Date,
+    Time,
+    Checkbox,
+    Rich,
+}
+
+/// How a field of a given type accepts input. Ids are only unique per field type,
+/// e.g. id 0 is "Anything" for text fields but "Standard dropdown" for selections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputType {
+    Anything,
+    Integer,
+    Money,
+    Alphanumeric,
+    Decimal,
+    Url,
+    Password,
+    StandardDropdown,
+    TreeDropdown,
+    RadioSelection,
+    DateOnly,
+    DateAndTime,
+    Default,
+}
+
+impl FieldType {
+    pub const ALL: [FieldType; 8] = [
+        FieldType::Text,
+        FieldType::Memo,
+        FieldType::SingleSelection,
+        FieldType::MultipleSelection,
+        FieldType::Date,
+        FieldType::Time,
+        FieldType::Checkbox,
+        FieldType::Rich,
+    ];
+
+    pub fn id(self) -> u8 {
+        match self {
+            FieldType::Text => 0,
+            FieldType::Memo => 1,
+            FieldType::SingleSelection => 2,
+            FieldType::MultipleSelection => 3,
+            FieldType::Date => 4,
+            FieldType::Time => 5,
+            FieldType::Checkbox => 6,
+            FieldType::Rich => 10,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|field_type| field_type.id() == id)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            FieldType::Text => "Text",
+            FieldType::Memo => "Memo",
+            FieldType::SingleSelection => "Single Selection",
+            FieldType::MultipleSelection => "Multiple Selection",
+            FieldType::Date => "Date",
+            FieldType::Time => "Time",
+            FieldType::Checkbox => "Checkbox",
+            FieldType::Rich => "Rich",
+        }
+    }
+
+    /// The single source of truth for which input types each field type accepts.
+    pub fn input_types(self) -> &'static [InputType] {
+        match self {
+            FieldType::Text => &[
+                InputType::Anything,
+                InputType::Integer,
+                InputType::Money,
+                InputType::Alphanumeric,
+                InputType::Decimal,
+                InputType::Url,
+                InputType::Password,
+            ],
+            FieldType::SingleSelection => &[
+                InputType::StandardDropdown,
+                InputType::TreeDropdown,
+                InputType::RadioSelection,
+            ],
+            FieldType::Date => &[
+                InputType::DateOnly,
+                InputType::DateAndTime,
+            ],
+            FieldType::Memo
+            | FieldType::MultipleSelection
+            | FieldType::Time
+            | FieldType::Checkbox
+            | FieldType::Rich => &[InputType::Default],
+        }
+    }
+
+    pub fn requires_options(self) -> bool {
+        matches!(self, FieldType::SingleSelection | FieldType::MultipleSelection)
+    }
+
+    // Accepted spellings besides the display name, already normalized
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            FieldType::Text => &["string"],
+            FieldType::Memo => &["multiline", "textarea"],
+            FieldType::SingleSelection => &["single", "singleselect", "dropdown", "select"],
+            FieldType::MultipleSelection => &["multiple", "multiselect", "multiselection", "multipleselect"],
+            FieldType::Date => &[],
+            FieldType::Time => &[],
+            FieldType::Checkbox => &["boolean", "bool", "tickbox"],
+            FieldType::Rich => &["richtext", "html"],
+        }
+    }
+
+    /// The field type written in a `type_id` column, see `resolve`.
+    pub fn parse(value: &str) -> Option<Self> {
+        resolve(value, Self::ALL.iter().map(|field_type| (*field_type, field_type.id(), field_type.display_name(), field_type.aliases())))
+    }
+
+    /// The known field type whose name is closest to a value that failed to parse.
+    pub fn suggest(value: &str) -> Option<Self> {
+        closest(value, Self::ALL.iter().map(|field_type| (*field_type, field_type.display_name(), field_type.aliases())))
+    }
+}
+
+impl InputType {
+    pub fn id(self) -> u8 {
+        match self {
+            InputType::Anything => 0,
+            InputType::Integer => 1,
+            InputType::Money => 2,
+            InputType::Alphanumeric => 3,
+            InputType::Decimal => 4,
+            InputType::Url => 5,
+            InputType::Password => 6,
+            InputType::StandardDropdown => 0,
+            InputType::TreeDropdown => 1,
+            InputType::RadioSelection => 2,
+            InputType::DateOnly => 0,
+            InputType::DateAndTime => 1,
+            InputType::Default => 0,
+        }
+    }
+
+    pub fn from_id(field_type: FieldType, id: u8) -> Option<Self> {
+        field_type.input_types().iter().copied().find(|input_type| input_type.id() == id)
+    }
+
+    // Accepted spellings besides the display name, already normalized
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            InputType::Anything => &["any", "text"],
+            InputType::Integer => &["int", "number", "wholenumber"],
+            InputType::Money => &["currency"],
+            InputType::Alphanumeric => &["alpha"],
+            InputType::Decimal => &["float"],
+            InputType::Url => &["link", "website"],
+            InputType::Password => &["secret"],
+            InputType::StandardDropdown => &["standard", "dropdown"],
+            InputType::TreeDropdown => &["tree", "treeview"],
+            InputType::RadioSelection => &["radio", "radiobutton", "radiobuttons"],
+            InputType::DateOnly => &["date"],
+            InputType::DateAndTime => &["datetime", "timestamp"],
+            InputType::Default => &["none", "standard"],
+        }
+    }
+
+    /// Only the input types the given field type accepts are considered, since ids repeat across types.
+    pub fn parse(field_type: FieldType, value: &str) -> Option<Self> {
+        resolve(value, field_type.input_types().iter().map(|input_type| (*input_type, input_type.id(), input_type.display_name(), input_type.aliases())))
+    }
+
+    /// The input type of the given field type whose name is closest to a value that failed to parse.
+    pub fn suggest(field_type: FieldType, value: &str) -> Option<Self> {
+        closest(value, field_type.input_types().iter().map(|input_type| (*input_type, input_type.display_name(), input_type.aliases())))
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            InputType::Anything => "Anything",
+            InputType::Integer => "Integer",
+            InputType::Money => "Money",
+            InputType::Alphanumeric => "Alphanumeric",
+            InputType::Decimal => "Decimal",
+            InputType::Url => "URL",
+            InputType::Password => "Password",
+            InputType::StandardDropdown => "Standard dropdown",
+            InputType::TreeDropdown => "Tree dropdown",
+            InputType::RadioSelection => "Radio selection",
+            InputType::DateOnly => "Date only",
+            InputType::DateAndTime => "Date and time",
+            InputType::Default => "Default",
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.display_name(), self.id())
+    }
+}
+
+impl fmt::Display for InputType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.display_name(), self.id())
+    }
+}
+
+pub(crate) fn normalize(value: &str) -> String {
+    value.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Finds the candidate with the given numeric id, display name or alias, ignoring
+/// case, spaces, `-` and `_`. Candidates are `(value, id, display name, aliases)`.
+pub(crate) fn resolve<T>(value: &str, mut candidates: impl Iterator<Item = (T, u8, &'static str, &'static [&'static str])>) -> Option<T> {
+    if let Ok(wanted) = value.trim().parse::<u8>() {
+        return candidates.find(|(_, id, _, _)| *id == wanted).map(|(candidate, ..)| candidate);
+    }
+
+    let wanted = normalize(value);
+    candidates
+        .find(|(_, _, display_name, aliases)| normalize(display_name) == wanted || aliases.contains(&wanted.as_str()))
+        .map(|(candidate, ..)| candidate)
+}
+
+pub(crate) fn closest<T>(value: &str, candidates: impl Iterator<Item = (T, &'static str, &'static [&'static str])>) -> Option<T> {
+    let wanted = normalize(value);
+    if wanted.is_empty() || wanted.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    candidates
+        .map(|(candidate, display_name, aliases)| {
+            let similarity = std::iter::once(normalize(display_name))
+                .chain(aliases.iter().map(|alias| alias.to_string()))
+                .map(|name| strsim::jaro_winkler(&wanted, &name))
+                .fold(0.0, f64::max);
+            (candidate, similarity)
+        })
+        .filter(|(_, similarity)| *similarity >= SUGGESTION_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entity::Entity;
+
+    #[test]
+    fn resolves_ids_display_names_and_aliases() {
+        assert_eq!(FieldType::parse("2"), Some(FieldType::SingleSelection));
+        assert_eq!(FieldType::parse(" rich-text "), Some(FieldType::Rich));
+        assert_eq!(FieldType::parse("42"), None);
+        assert_eq!(Entity::parse("Users"), Some(Entity::User));
+        assert_eq!(Entity::parse("9"), Some(Entity::Item));
+    }
+
+    #[test]
+    fn input_types_resolve_within_their_field_type() {
+        assert_eq!(InputType::parse(FieldType::Text, "1"), Some(InputType::Integer));
+        assert_eq!(InputType::parse(FieldType::SingleSelection, "1"), Some(InputType::TreeDropdown));
+        assert_eq!(InputType::parse(FieldType::SingleSelection, "Tree_Dropdown"), Some(InputType::TreeDropdown));
+        assert_eq!(InputType::parse(FieldType::Text, "tree"), None);
+    }
+}
@@ -0,0 +1,187 @@
+use serde::{Serialize, Deserialize};
+use crate::models::entity::Entity;
+use crate::models::field::{Field, FieldFlags};
+use crate::models::option_list::OptionList;
+use crate::models::field_type::{FieldType, InputType};
+
+/// Prefix Halo puts in front of every custom field name on creation. Names in
+/// the input file are written without it.
+pub const HALO_NAME_PREFIX: &str = "CF";
+
+/// A custom field as returned by Halo's `/fieldinfo` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HaloField {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(rename = "type", default)]
+    pub type_id: u8,
+    #[serde(rename = "inputtype", default)]
+    pub input_type_id: u8,
+    #[serde(default)]
+    pub usage: u8,
+    #[serde(default)]
+    pub values: Vec<HaloFieldValue>,
+    // Not every Halo version returns these, `None` means unknown rather than off
+    #[serde(default)]
+    pub searchable: Option<bool>,
+    #[serde(default)]
+    pub user_searchable: Option<bool>,
+    #[serde(default)]
+    pub calendar_searchable: Option<bool>,
+    #[serde(default)]
+    pub copytochild: Option<bool>,
+    #[serde(default)]
+    pub copytochildonupdate: Option<bool>,
+}
+
+/// A selectable option of a single or multiple selection field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HaloFieldValue {
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+}
+
+/// The field Halo returns after creating it, with the ids it assigned to the
+/// field and its values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedField {
+    pub id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub values: Vec<HaloFieldValue>,
+}
+
+impl CreatedField {
+    /// Fields are created by posting a list, Halo answers with the saved field or a list holding it.
+    pub fn parse(body: &str) -> Result<Self, serde_json::Error> {
+        match serde_json::from_str::<serde_json::Value>(body)? {
+            serde_json::Value::Array(mut fields) if !fields.is_empty() => serde_json::from_value(fields.swap_remove(0)),
+            other => serde_json::from_value(other),
+        }
+    }
+}
+
+impl HaloField {
+    /// Halo prefixes custom field names with `CF`, so `Region` is stored as `CFRegion`.
+    /// Names are compared case-insensitively with and without that prefix.
+    pub fn matches_name(&self, name: &str) -> bool {
+        let existing = self.name.to_lowercase();
+        let wanted = name.to_lowercase();
+
+        existing == wanted
+            || existing.strip_prefix("cf") == Some(wanted.as_str())
+            || wanted.strip_prefix("cf") == Some(existing.as_str())
+    }
+
+    /// Case-insensitive wildcard match (`*` any run of characters, `?` a single one)
+    /// against the name with and without the `CF` prefix. An empty pattern matches all.
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+        if pattern.is_empty() {
+            return true;
+        }
+
+        let name = self.name.to_lowercase();
+        let unprefixed = name.strip_prefix("cf").unwrap_or(&name);
+
+        [name.as_str(), unprefixed].iter()
+            .any(|candidate| Self::wildcard_match(&pattern, &candidate.chars().collect::<Vec<_>>()))
+    }
+
+    fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some('*'), _) => {
+                Self::wildcard_match(&pattern[1..], text)
+                    || (!text.is_empty() && Self::wildcard_match(pattern, &text[1..]))
+            },
+            (Some('?'), Some(_)) => Self::wildcard_match(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p == t => Self::wildcard_match(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+
+    /// Option names in the order Halo returns them, tree dropdown levels included as flat values.
+    pub fn options(&self) -> OptionList {
+        OptionList::from_names(self.values.iter().map(|value| value.name.as_str()))
+    }
+
+    /// The field's flags, falling back to the defaults for any Halo did not return.
+    pub fn flags(&self) -> FieldFlags {
+        let defaults = FieldFlags::default();
+        FieldFlags {
+            searchable: self.searchable.unwrap_or(defaults.searchable),
+            user_searchable: self.user_searchable.unwrap_or(defaults.user_searchable),
+            calendar_searchable: self.calendar_searchable.unwrap_or(defaults.calendar_searchable),
+            copy_to_child: self.copytochild.unwrap_or(defaults.copy_to_child),
+            copy_to_child_on_update: self.copytochildonupdate.unwrap_or(defaults.copy_to_child_on_update),
+        }
+    }
+
+    /// The entity the field belongs to, `None` for usages this tool doesn't know.
+    /// Halo versions that don't report a usage only have ticket fields.
+    pub fn entity(&self) -> Option<Entity> {
+        match self.usage {
+            0 => Some(Entity::default()),
+            usage => Entity::from_id(usage),
+        }
+    }
+
+    /// Maps the field back into the source file model. Fails with the reason for fields
+    /// the input file cannot express, so an export can always be re-imported as is.
+    pub fn to_field(&self) -> Result<Field, String> {
+        let unsupported = || format!("type {} or usage {} is not supported by the input file", self.type_id, self.usage);
+        let field_type = FieldType::from_id(self.type_id).ok_or_else(unsupported)?;
+        let input_type = InputType::from_id(field_type, self.input_type_id).ok_or_else(unsupported)?;
+        let usage = self.entity().ok_or_else(unsupported)?;
+
+        // Halo's values don't say which level they belong to, so the tree would come back flat
+        if input_type == InputType::TreeDropdown && !self.values.is_empty() {
+            return Err("tree dropdown levels can't be exported yet".to_string());
+        }
+
+        // Halo adds the CF prefix itself when the field is created again
+        let name = match self.name.strip_prefix(HALO_NAME_PREFIX) {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => self.name.clone(),
+        };
+
+        let options = if field_type.requires_options() {
+            self.options()
+        } else {
+            OptionList::default()
+        };
+
+        Ok(Field::new(
+            name,
+            self.label.clone(),
+            field_type,
+            input_type,
+            options,
+            usage,
+            self.flags(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> HaloField {
+        serde_json::from_value(serde_json::json!({ "id": 1, "name": name })).unwrap()
+    }
+
+    #[test]
+    fn names_match_with_or_without_the_cf_prefix_ignoring_case() {
+        assert!(named("CFRegion").matches_name("Region"));
+        assert!(named("CFRegion").matches_name("cfregion"));
+        assert!(named("Region").matches_name("CFRegion"));
+        assert!(!named("CFRegion").matches_name("Regions"));
+        assert!(!named("CFCFRegion").matches_name("Region"));
+    }
+}
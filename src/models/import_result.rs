@@ -1,19 +1,73 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
 use chrono::{DateTime, Local};
 use serde::Serialize;
 use colored::*;
+use crate::error::{Result, CustomError, ConfigErrorKind, IOErrorKind};
+use crate::models::entity::Entity;
+use crate::models::run_journal::{RunJournal, FieldStatus};
 
 #[derive(Debug, Serialize)]
 pub struct FieldResult {
     pub label: String,
+    pub usage: Option<Entity>,  // `None` for instance fields with a usage this tool doesn't know
+    pub halo_id: Option<i64>,  // id of the field on the instance, `None` when unknown
     pub success: bool,
     pub error: Option<String>,
+    pub attempts: u32,
+    pub duration_ms: u128,  // time spent on the API call, retries included
+    #[serde(skip)]
+    pub payload: Option<String>,  // request body sent, `None` when nothing was sent
     pub timestamp: DateTime<Local>,
 }
 
+// One field of an exported report, `success` alone can't tell failed and skipped fields apart
+#[derive(Serialize)]
+struct ReportEntry<'a> {
+    status: &'static str,
+    #[serde(flatten)]
+    result: &'a FieldResult,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    successful: usize,
+    failed: usize,
+    skipped: usize,
+    fields: Vec<ReportEntry<'a>>,
+}
+
+/// The formats `write_report` writes, picked by the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    JUnit,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path).extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("json") => Ok(ReportFormat::Json),
+            Some("xml") => Ok(ReportFormat::JUnit),
+            Some("csv") => Ok(ReportFormat::Csv),
+            _ => Err(CustomError::ConfigError(ConfigErrorKind::UnknownReportFormat(path.to_string()))),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ImportResults {
     pub successful: Vec<FieldResult>,
     pub failed: Vec<FieldResult>,
+    pub skipped: Vec<FieldResult>,
+    // Updated with every result so an interrupted import can be resumed
+    journal: Option<RunJournal>,
 }
 
 impl ImportResults {
@@ -21,27 +75,185 @@ impl ImportResults {
         Self {
             successful: Vec::new(),
             failed: Vec::new(),
+            skipped: Vec::new(),
+            journal: None,
+        }
+    }
+
+    pub fn with_journal(journal: Option<RunJournal>) -> Self {
+        Self { journal, ..Self::new() }
+    }
+
+    fn record(&mut self, label: &str, status: FieldStatus, error: Option<&str>, attempts: u32, halo_id: Option<i64>) {
+        if let Some(journal) = &mut self.journal {
+            journal.record(label, status, error.map(str::to_string), attempts, halo_id);
         }
     }
 
-    pub fn add_success(&mut self, label: String) {
+    pub fn add_success(
+        &mut self,
+        label: String,
+        usage: Option<Entity>,
+        halo_id: Option<i64>,
+        attempts: u32,
+        duration: Duration,
+        payload: Option<String>,
+    ) {
+        self.record(&label, FieldStatus::Succeeded, None, attempts, halo_id);
         self.successful.push(FieldResult {
             label,
+            usage,
+            halo_id,
             success: true,
             error: None,
+            attempts,
+            duration_ms: duration.as_millis(),
+            payload,
             timestamp: Local::now(),
         });
     }
 
-    pub fn add_failure(&mut self, label: String, error: String) {
+    pub fn add_failure(
+        &mut self,
+        label: String,
+        usage: Option<Entity>,
+        error: String,
+        attempts: u32,
+        duration: Duration,
+        payload: Option<String>,
+    ) {
+        self.record(&label, FieldStatus::Failed, Some(&error), attempts, None);
         self.failed.push(FieldResult {
             label,
+            usage,
+            halo_id: None,
             success: false,
             error: Some(error),
+            attempts,
+            duration_ms: duration.as_millis(),
+            payload,
+            timestamp: Local::now(),
+        });
+    }
+
+    /// `halo_id` is the id of the field when it already exists on the instance.
+    pub fn add_skipped(&mut self, label: String, usage: Option<Entity>, halo_id: Option<i64>, reason: String) {
+        self.record(&label, FieldStatus::Skipped, Some(&reason), 0, halo_id);
+        self.skipped.push(FieldResult {
+            label,
+            usage,
+            halo_id,
+            success: false,
+            error: Some(reason),
+            attempts: 0,
+            duration_ms: 0,
+            payload: None,
             timestamp: Local::now(),
         });
     }
 
+    /// Every result with its status, in the order the fields were processed.
+    pub fn in_order(&self) -> Vec<(&'static str, &FieldResult)> {
+        let mut results: Vec<_> = self.successful.iter().map(|result| ("succeeded", result))
+            .chain(self.failed.iter().map(|result| ("failed", result)))
+            .chain(self.skipped.iter().map(|result| ("skipped", result)))
+            .collect();
+        results.sort_by_key(|(_, result)| result.timestamp);
+        results
+    }
+
+    fn entries(&self) -> Vec<ReportEntry<'_>> {
+        self.in_order().into_iter()
+            .map(|(status, result)| ReportEntry { status, result })
+            .collect()
+    }
+
+    /// Writes the results as JSON (`.json`), JUnit XML (`.xml`) or CSV (`.csv`).
+    pub fn write_report(&self, path: &str) -> Result<()> {
+        let content = match ReportFormat::from_path(path)? {
+            ReportFormat::Json => serde_json::to_string_pretty(&JsonReport {
+                successful: self.successful.len(),
+                failed: self.failed.len(),
+                skipped: self.skipped.len(),
+                fields: self.entries(),
+            })?,
+            ReportFormat::JUnit => self.to_junit(),
+            ReportFormat::Csv => return self.write_csv(path),
+        };
+
+        fs::write(path, content).map_err(|e|
+            CustomError::IOError(IOErrorKind::WriteFile(format!("{}: {}", path, e)))
+        )
+    }
+
+    fn write_csv(&self, path: &str) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(["status", "label", "usage", "halo_id", "attempts", "duration_ms", "error", "timestamp"])?;
+
+        for entry in self.entries() {
+            let result = entry.result;
+            writer.write_record([
+                entry.status,
+                result.label.as_str(),
+                result.usage.map(|usage| usage.display_name()).unwrap_or_default(),
+                &result.halo_id.map(|id| id.to_string()).unwrap_or_default(),
+                &result.attempts.to_string(),
+                &result.duration_ms.to_string(),
+                result.error.as_deref().unwrap_or_default(),
+                &result.timestamp.to_rfc3339(),
+            ])?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    // One testcase per field, grouped by entity through the class name
+    fn to_junit(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let total = self.successful.len() + self.failed.len() + self.skipped.len();
+        let counts = format!("tests=\"{}\" failures=\"{}\" skipped=\"{}\"", total, self.failed.len(), self.skipped.len());
+
+        xml.push_str(&format!("<testsuites name=\"halo_custom_field_builder\" {}>\n", counts));
+        // JUnit times are in seconds, the suite's is the time spent on API calls
+        let seconds = |duration_ms: u128| format!("{:.3}", duration_ms as f64 / 1000.0);
+        let entries = self.entries();
+        let total_ms: u128 = entries.iter().map(|entry| entry.result.duration_ms).sum();
+        xml.push_str(&format!("  <testsuite name=\"Custom field import\" {} time=\"{}\" timestamp=\"{}\">\n",
+            counts,
+            seconds(total_ms),
+            Local::now().format("%Y-%m-%dT%H:%M:%S")
+        ));
+
+        for entry in entries {
+            let result = entry.result;
+            let class_name = result.usage.map(|usage| usage.display_name()).unwrap_or("Other");
+            let error = xml_escape(result.error.as_deref().unwrap_or_default());
+            let open = format!("    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+                class_name,
+                xml_escape(&result.label),
+                seconds(result.duration_ms)
+            );
+            let properties = result.halo_id
+                .map(|id| format!("      <properties>\n        <property name=\"halo_id\" value=\"{}\"/>\n      </properties>\n", id))
+                .unwrap_or_default();
+
+            match entry.status {
+                "failed" => xml.push_str(&format!(
+                    "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n", open, error, error
+                )),
+                "skipped" => xml.push_str(&format!(
+                    "{}>\n      <skipped message=\"{}\"/>\n    </testcase>\n", open, error
+                )),
+                _ if properties.is_empty() => xml.push_str(&format!("{}/>\n", open)),
+                _ => xml.push_str(&format!("{}>\n{}    </testcase>\n", open, properties)),
+            }
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
     pub fn log_summary(&self) {
         println!("\n{}", "Import Summary:".bright_blue().bold());
         println!("{}", "=".repeat(80).bright_blue());
@@ -55,17 +267,97 @@ impl ImportResults {
         println!("• Failed imports: {}", 
             self.failed.len().to_string().bright_red()
         );
+        println!("• Skipped fields: {}", 
+            self.skipped.len().to_string().bright_cyan()
+        );
+        println!("• Fields that needed retries: {}", 
+            self.successful.iter().chain(&self.failed)
+                .filter(|r| r.attempts > 1)
+                .count().to_string().bright_yellow()
+        );
+
+        self.log_entity_summary();
+
+        if !self.successful.is_empty() {
+            println!("\n{}", "Successful Fields:".bright_green().bold());
+            for result in &self.successful {
+                println!("• {}{}", result.label.bright_yellow(), id_suffix(result.halo_id));
+            }
+        }
 
         if !self.failed.is_empty() {
             println!("\n{}", "Failed Fields:".bright_red().bold());
             for result in &self.failed {
+                println!("• {} ({}, after {} attempt(s))", 
+                    result.label.bright_yellow(),
+                    result.error.as_ref().unwrap().bright_red(),
+                    result.attempts
+                );
+            }
+        }
+
+        if !self.skipped.is_empty() {
+            println!("\n{}", "Skipped Fields:".bright_cyan().bold());
+            for result in &self.skipped {
                 println!("• {} ({})", 
                     result.label.bright_yellow(),
-                    result.error.as_ref().unwrap().bright_red()
+                    result.error.as_deref().unwrap_or_default().bright_cyan()
+                );
+            }
+        }
+
+        if let Some(journal) = &self.journal {
+            println!("\n• Run journal: {}", journal.path().display().to_string().bright_yellow());
+            let open = journal.open_count();
+            if open > 0 {
+                println!("• {} field(s) still pending or failed, continue with: {}",
+                    open.to_string().bright_yellow(),
+                    format!("--resume {}", journal.run_id).bright_green()
                 );
             }
         }
         
         println!("{}", "=".repeat(80).bright_blue());
     }
-}
\ No newline at end of file
+
+    // Counts per entity, in the order of `Entity::ALL` with unknown usages last
+    fn log_entity_summary(&self) {
+        let count = |results: &[FieldResult], usage: Option<Entity>| {
+            results.iter().filter(|r| r.usage == usage).count()
+        };
+        let usages: Vec<_> = Entity::ALL.into_iter().map(Some)
+            .chain(std::iter::once(None))
+            .filter(|usage| {
+                count(&self.successful, *usage) + count(&self.failed, *usage) + count(&self.skipped, *usage) > 0
+            })
+            .collect();
+
+        if usages.is_empty() {
+            return;
+        }
+
+        println!("\n{}", "By Entity:".bright_blue().bold());
+        for usage in usages {
+            println!("• {}: {} successful, {} failed, {} skipped",
+                usage.map(|entity| entity.display_name()).unwrap_or("Other").bright_yellow(),
+                count(&self.successful, usage).to_string().bright_green(),
+                count(&self.failed, usage).to_string().bright_red(),
+                count(&self.skipped, usage).to_string().bright_cyan()
+            );
+        }
+    }
+}
+
+/// ` (id N)` for log lines about a field whose Halo id is known, empty otherwise.
+pub fn id_suffix(halo_id: Option<i64>) -> String {
+    halo_id.map(|id| format!(" (id {})", id)).unwrap_or_default()
+}
+
+// Also safe for HTML text and attribute values
+pub(crate) fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -1,2 +1,9 @@
+pub mod entity;
 pub mod field;
-pub mod import_result;
\ No newline at end of file
+pub mod field_type;
+pub mod halo_field;
+pub mod import_result;
+pub mod option_list;
+pub mod preflight;
+pub mod run_journal;
+pub mod validation_report;
\ No newline at end of file
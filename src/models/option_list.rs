@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+use std::fmt;
+use serde::{Serialize, Deserialize};
+
+/// How options are ordered on the field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptionOrder {
+    #[default]
+    Written,
+    Alphabetical,
+}
+
+/// The parsed `options` column, option names separated by commas.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionList {
+    pub options: Vec<String>,
+}
+
+impl OptionOrder {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace(['-', '_'], " ").as_str() {
+            "" | "written" | "as written" | "file" => Some(OptionOrder::Written),
+            "alphabetical" | "alpha" | "sorted" | "a z" => Some(OptionOrder::Alphabetical),
+            _ => None,
+        }
+    }
+}
+
+impl OptionList {
+    /// Splits the column on commas, empty options (e.g. a trailing comma) are ignored.
+    pub fn parse(text: &str) -> Self {
+        OptionList {
+            options: text.split(',')
+                .map(str::trim)
+                .filter(|option| !option.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// A list of option names, as Halo reports them for existing fields.
+    pub fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        OptionList { options: names.into_iter().map(str::to_string).collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    pub fn sort(&mut self, order: OptionOrder) {
+        if order == OptionOrder::Alphabetical {
+            self.options.sort_by_key(|option| option.to_lowercase());
+        }
+    }
+
+    /// Options written more than once, compared without case.
+    pub fn duplicates(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        let mut duplicates = Vec::new();
+        for option in &self.options {
+            let key = option.to_lowercase();
+            if !seen.insert(key.clone()) && reported.insert(key) {
+                duplicates.push(option.clone());
+            }
+        }
+        duplicates
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.options.iter().map(String::as_str).collect()
+    }
+}
+
+/// Writes the list back in the syntax `parse` reads, which is also the format Halo accepts.
+impl fmt::Display for OptionList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.options.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_commas_and_ignores_empty_options() {
+        assert_eq!(OptionList::parse(" Small, Medium ,Large,").names(), ["Small", "Medium", "Large"]);
+        assert_eq!(OptionList::parse("A,,B").names(), ["A", "B"]);
+        assert!(OptionList::parse(" , ").is_empty());
+    }
+
+    #[test]
+    fn alphabetical_order_ignores_case() {
+        let mut list = OptionList::parse("medium,Large,small");
+        list.sort(OptionOrder::Written);
+        assert_eq!(list.names(), ["medium", "Large", "small"]);
+        list.sort(OptionOrder::Alphabetical);
+        assert_eq!(list.names(), ["Large", "medium", "small"]);
+    }
+
+    #[test]
+    fn option_order_accepts_aliases() {
+        assert_eq!(OptionOrder::parse(""), Some(OptionOrder::Written));
+        assert_eq!(OptionOrder::parse("As-Written"), Some(OptionOrder::Written));
+        assert_eq!(OptionOrder::parse("A_Z"), Some(OptionOrder::Alphabetical));
+        assert_eq!(OptionOrder::parse("random"), None);
+    }
+
+    #[test]
+    fn duplicates_ignore_case_and_are_reported_once() {
+        assert_eq!(OptionList::parse("Small,small,Large,SMALL").duplicates(), ["small"]);
+        assert!(OptionList::parse("Small,Large").duplicates().is_empty());
+    }
+
+    #[test]
+    fn display_round_trips_through_parse() {
+        let list = OptionList::parse("Small, Medium,Large");
+        assert_eq!(list.to_string(), "Small,Medium,Large");
+        assert_eq!(OptionList::parse(&list.to_string()), list);
+    }
+}
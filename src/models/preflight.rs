@@ -0,0 +1,45 @@
+use crate::models::field::Field;
+use crate::models::halo_field::HaloField;
+
+/// Outcome of comparing a field from the input file with the fields already on the instance.
+#[derive(Debug, Clone)]
+pub enum PreflightStatus {
+    New,
+    AlreadyPresent(i64),                                // existing field id
+    Conflict { existing_id: i64, existing_type: u8 },
+}
+
+impl PreflightStatus {
+    pub fn check(field: &Field, existing: &[HaloField]) -> Self {
+        match existing.iter().find(|halo_field| halo_field.matches_name(&field.name)) {
+            None => PreflightStatus::New,
+            Some(halo_field) if halo_field.type_id == field.field_type.id() => {
+                PreflightStatus::AlreadyPresent(halo_field.id)
+            },
+            Some(halo_field) => PreflightStatus::Conflict {
+                existing_id: halo_field.id,
+                existing_type: halo_field.type_id,
+            },
+        }
+    }
+
+    /// Id of the field on the instance that this row describes, a conflicting field is a different one.
+    pub fn existing_id(&self) -> Option<i64> {
+        match self {
+            PreflightStatus::AlreadyPresent(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn skip_reason(&self) -> Option<String> {
+        match self {
+            PreflightStatus::New => None,
+            PreflightStatus::AlreadyPresent(id) => Some(format!(
+                "Already present on the instance (id {})", id
+            )),
+            PreflightStatus::Conflict { existing_id, existing_type } => Some(format!(
+                "Conflicts with existing field id {} of type {}", existing_id, existing_type
+            )),
+        }
+    }
+}
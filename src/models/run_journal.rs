@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local};
+use log::warn;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use crate::config::Config;
+use crate::error::{Result, CustomError, IOErrorKind};
+use crate::models::field::Field;
+
+const RUNS_DIR: &str = "runs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldStatus {
+    Pending,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub name: String,
+    pub label: String,
+    pub status: FieldStatus,
+    pub error: Option<String>,
+    pub attempts: u32,
+    pub updated: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub halo_id: Option<i64>,  // set once the field has been created
+}
+
+/// Progress of an import, saved to `runs/<run id>.json` after every field so an
+/// interrupted run can be resumed with only the fields that are still open.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunJournal {
+    pub run_id: String,
+    pub source_file: String,
+    pub source_sheet: Option<String>,
+    pub file_hash: String,
+    pub started: DateTime<Local>,
+    pub entries: Vec<JournalEntry>,
+}
+
+/// SHA-256 of the input file, to tell whether it changed between runs.
+pub fn hash_file(path: &str) -> Result<String> {
+    let content = fs::read(path).map_err(|e|
+        CustomError::IOError(IOErrorKind::ReadFile(format!("{}: {}", path, e)))
+    )?;
+    Ok(Sha256::digest(&content).iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+fn journal_path(run_id: &str) -> PathBuf {
+    Path::new(RUNS_DIR).join(format!("{}.json", run_id))
+}
+
+impl RunJournal {
+    /// Creates the journal of a new run with every field pending.
+    pub fn start(config: &Config, fields: &[Field]) -> Result<Self> {
+        // Sortable by start time, the random part keeps runs started in the same second apart
+        let run_id = format!("{}-{:04x}", Local::now().format("%Y%m%d-%H%M%S"), rand::thread_rng().gen::<u16>());
+
+        let journal = RunJournal {
+            run_id,
+            source_file: config.source_file_name.clone(),
+            source_sheet: config.source_sheet.clone(),
+            file_hash: hash_file(&config.source_file_name)?,
+            started: Local::now(),
+            entries: fields.iter()
+                .map(|field| JournalEntry {
+                    name: field.name.clone(),
+                    label: field.label.clone(),
+                    status: FieldStatus::Pending,
+                    error: None,
+                    attempts: 0,
+                    updated: None,
+                    halo_id: None,
+                })
+                .collect(),
+        };
+
+        fs::create_dir_all(RUNS_DIR).map_err(|e| CustomError::IOError(IOErrorKind::CreateDir(e.to_string())))?;
+        journal.save()?;
+        Ok(journal)
+    }
+
+    pub fn load(run_id: &str) -> Result<Self> {
+        let path = journal_path(run_id);
+        let text = fs::read_to_string(&path).map_err(|e|
+            CustomError::InvalidRunJournal(run_id.to_string(), format!("{}: {}", path.display(), e))
+        )?;
+        serde_json::from_str(&text).map_err(|e| CustomError::InvalidRunJournal(run_id.to_string(), e.to_string()))
+    }
+
+    /// Fails when the input file was edited since the run started, as the journal
+    /// would no longer describe the fields being imported.
+    pub fn verify_source(&self) -> Result<()> {
+        if hash_file(&self.source_file)? != self.file_hash {
+            return Err(CustomError::InvalidRunJournal(self.run_id.clone(), format!(
+                "'{}' has changed since the run started. Start a new import instead, existing fields are skipped",
+                self.source_file
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn path(&self) -> PathBuf {
+        journal_path(&self.run_id)
+    }
+
+    /// Pending and failed fields are imported again when the run is resumed.
+    pub fn is_open(&self, label: &str) -> bool {
+        self.entries.iter()
+            .find(|entry| entry.label == label)
+            .is_none_or(|entry| matches!(entry.status, FieldStatus::Pending | FieldStatus::Failed))
+    }
+
+    pub fn open_count(&self) -> usize {
+        self.entries.iter().filter(|entry| self.is_open(&entry.label)).count()
+    }
+
+    // A journal that can't be written must not stop an import that is already creating fields
+    pub fn record(&mut self, label: &str, status: FieldStatus, error: Option<String>, attempts: u32, halo_id: Option<i64>) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.label == label) {
+            entry.status = status;
+            entry.error = error;
+            entry.attempts += attempts;
+            entry.updated = Some(Local::now());
+            entry.halo_id = halo_id.or(entry.halo_id);
+        }
+
+        if let Err(e) = self.save() {
+            warn!("Failed to update run journal {}: {}", self.path().display(), e);
+        }
+    }
+
+    // Written next to the journal and renamed over it, so a crash mid-write leaves the
+    // previous journal intact instead of a truncated one
+    fn save(&self) -> Result<()> {
+        let path = self.path();
+        let temp = path.with_extension("json.tmp");
+        let write_error = |e: std::io::Error| CustomError::IOError(IOErrorKind::WriteFile(format!("{}: {}", path.display(), e)));
+
+        fs::write(&temp, serde_json::to_string_pretty(self)?).map_err(write_error)?;
+        fs::rename(&temp, &path).map_err(write_error)
+    }
+}
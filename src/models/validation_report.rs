@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use crate::error::{Result, CustomError, FieldError, IOErrorKind, RecordLocation};
+use crate::lint::Severity;
+
+/// Every problem found while validating an input file, collected so the whole
+/// file can be fixed in one pass instead of one error per run. Warnings come
+/// from lint rules and are reported without failing validation.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<FieldError>,
+    pub warnings: Vec<FieldError>,
+}
+
+// One line of an exported report
+#[derive(Serialize)]
+struct ReportEntry<'a> {
+    row: usize,
+    column: &'a str,
+    cell: Option<&'a str>,
+    severity: Severity,
+    kind: &'static str,
+    value: &'a str,
+    message: String,
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, error: FieldError) {
+        self.errors.push(error);
+    }
+
+    pub fn add_warning(&mut self, warning: FieldError) {
+        self.warnings.push(warning);
+    }
+
+    /// True when there are no errors, warnings alone don't fail validation.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    // Errors and warnings of the same field definition, errors first
+    fn entries(&self) -> impl Iterator<Item = (Severity, &FieldError)> {
+        self.errors.iter().map(|error| (Severity::Error, error))
+            .chain(self.warnings.iter().map(|warning| (Severity::Warning, warning)))
+    }
+
+    fn by_location(&self) -> BTreeMap<&RecordLocation, Vec<(Severity, &FieldError)>> {
+        let mut records: BTreeMap<&RecordLocation, Vec<(Severity, &FieldError)>> = BTreeMap::new();
+        for (severity, error) in self.entries() {
+            records.entry(&error.location).or_default().push((severity, error));
+        }
+        records
+    }
+
+    /// Writes the report as JSON when the path ends in `.json`, as CSV otherwise.
+    pub fn write(&self, path: &str) -> Result<()> {
+        let entries: Vec<_> = self.by_location().into_values()
+            .flatten()
+            .map(|(severity, error)| ReportEntry {
+                row: error.location.number(),
+                column: &error.column,
+                cell: error.cell.as_deref(),
+                severity,
+                kind: error.error.name(),
+                value: &error.value,
+                message: error.error.to_string(),
+            })
+            .collect();
+
+        let is_json = Path::new(path).extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            fs::write(path, serde_json::to_string_pretty(&entries)?).map_err(|e|
+                CustomError::IOError(IOErrorKind::WriteFile(format!("{}: {}", path, e)))
+            )?;
+        } else {
+            let mut writer = csv::Writer::from_path(path)?;
+            for entry in &entries {
+                writer.serialize(entry)?;
+            }
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let records = self.by_location();
+        write!(f, "Found {} validation error(s)", self.errors.len())?;
+        if !self.warnings.is_empty() {
+            write!(f, " and {} warning(s)", self.warnings.len())?;
+        }
+        write!(f, " in {} field definition(s)", records.len())?;
+
+        for (location, errors) in records {
+            write!(f, "\n\n{}:", location)?;
+            for (severity, error) in errors {
+                let marker = if severity == Severity::Warning { "⚠" } else { "•" };
+                let message = error.error.to_string().replace('\n', "\n      ");
+                match &error.cell {
+                    Some(cell) => write!(f, "\n  {} {} ({}) = '{}': {}", marker, error.column, cell, error.value, message)?,
+                    None => write!(f, "\n  {} {} = '{}': {}", marker, error.column, error.value, message)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
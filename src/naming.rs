@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Derives field names from labels for rows that leave `name` blank, e.g.
+/// `Customer's Région (EU)` becomes `AcmeCustomersRegionEU` with prefix `Acme`.
+pub struct NameGenerator {
+    prefix: String,
+    max_length: usize,
+    // Lowercase names already in use, Halo compares names without case
+    taken: HashSet<String>,
+}
+
+impl NameGenerator {
+    /// `written` are the names filled in by hand, generated names never reuse them.
+    pub fn new<'a>(prefix: &str, max_length: usize, written: impl IntoIterator<Item = &'a str>) -> Self {
+        NameGenerator {
+            prefix: prefix.to_string(),
+            max_length,
+            taken: written.into_iter().map(str::to_lowercase).collect(),
+        }
+    }
+
+    /// Fails with the reason when no name within `max_length` can be built from the label.
+    pub fn generate(&mut self, label: &str) -> Result<String, String> {
+        let base = pascal_case(label);
+        if base.is_empty() {
+            return Err("it has no letters or numbers".to_string());
+        }
+
+        let prefix_length = self.prefix.chars().count();
+        if prefix_length >= self.max_length {
+            return Err(format!(
+                "the prefix '{}' leaves no room for it within the {} character limit", self.prefix, self.max_length
+            ));
+        }
+
+        // The suffix goes after the truncated base so every candidate stays within the limit,
+        // candidates stop once the suffix leaves no room for the base
+        let name = (1..)
+            .map_while(|attempt| {
+                let suffix = if attempt == 1 { String::new() } else { attempt.to_string() };
+                let room = self.max_length.checked_sub(prefix_length + suffix.len()).filter(|room| *room > 0)?;
+                Some(format!("{}{}{}", self.prefix, base.chars().take(room).collect::<String>(), suffix))
+            })
+            .find(|candidate| !self.taken.contains(&candidate.to_lowercase()))
+            .ok_or_else(|| format!("every name within the {} character limit is already taken", self.max_length))?;
+
+        self.taken.insert(name.to_lowercase());
+        Ok(name)
+    }
+}
+
+// Letters without an accent to strip, which decomposition leaves untouched
+fn transliterate(c: char) -> Option<&'static str> {
+    match c {
+        'ß' => Some("ss"),
+        'æ' => Some("ae"),
+        'Æ' => Some("Ae"),
+        'œ' => Some("oe"),
+        'Œ' => Some("Oe"),
+        'ø' => Some("o"),
+        'Ø' => Some("O"),
+        'đ' => Some("d"),
+        'Đ' => Some("D"),
+        'ł' => Some("l"),
+        'Ł' => Some("L"),
+        'þ' => Some("th"),
+        'Þ' => Some("Th"),
+        _ => None,
+    }
+}
+
+fn pascal_case(label: &str) -> String {
+    let plain: String = label.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        // Apostrophes join the word, "Customer's" becomes "Customers" rather than "CustomerS"
+        .filter(|c| !matches!(c, '\'' | '’'))
+        .flat_map(|c| match transliterate(c) {
+            Some(text) => text.chars().collect::<Vec<_>>(),
+            None => vec![c],
+        })
+        .collect();
+
+    plain.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_the_documented_example() {
+        let mut generator = NameGenerator::new("Acme", 30, []);
+        assert_eq!(generator.generate("Customer's Région (EU)").unwrap(), "AcmeCustomersRegionEU");
+        assert_eq!(generator.generate("Größe & Maße").unwrap(), "AcmeGrosseMasse");
+    }
+
+    #[test]
+    fn taken_names_get_a_number_ignoring_case() {
+        let mut generator = NameGenerator::new("", 30, ["region"]);
+        assert_eq!(generator.generate("Region").unwrap(), "Region2");
+        assert_eq!(generator.generate("Region").unwrap(), "Region3");
+    }
+
+    #[test]
+    fn the_suffix_still_fits_within_max_length() {
+        let mut generator = NameGenerator::new("Ac", 8, ["AcRegion"]);
+        let name = generator.generate("Region Name").unwrap();
+        assert_eq!(name, "AcRegio2");
+        assert!(name.chars().count() <= 8);
+    }
+
+    #[test]
+    fn fails_once_every_name_within_the_limit_is_taken() {
+        let mut generator = NameGenerator::new("", 2, ["re", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9"]);
+        assert!(generator.generate("Region").unwrap_err().contains("already taken"));
+    }
+
+    #[test]
+    fn a_prefix_as_long_as_the_limit_leaves_no_room() {
+        let mut generator = NameGenerator::new("Acme", 4, []);
+        assert!(generator.generate("Region").unwrap_err().contains("leaves no room"));
+    }
+
+    #[test]
+    fn a_label_without_letters_or_numbers_has_no_name() {
+        let mut generator = NameGenerator::new("Acme", 30, []);
+        assert_eq!(generator.generate("?! - ()").unwrap_err(), "it has no letters or numbers");
+    }
+}
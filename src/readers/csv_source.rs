@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use csv::{Reader, StringRecord};
+use crate::error::{Result, CustomError, RecordLocation};
+use super::{FieldRecord, FieldSource, OPTIONAL_COLUMNS};
+
+pub struct CsvSource {
+    path: String,
+}
+
+#[derive(Debug)]
+struct FieldPositions {
+    // Names can be generated from labels, so the column may be left out
+    name: Option<usize>,
+    label: usize,
+    type_id: usize,
+    input_type_id: usize,
+    options: usize,
+    optional: Vec<(&'static str, usize)>,
+}
+
+impl CsvSource {
+    pub fn new(path: &str) -> Self {
+        CsvSource { path: path.to_string() }
+    }
+
+    fn get_field_positions(&self, headers: &StringRecord) -> Result<FieldPositions> {
+        Ok(FieldPositions {
+            name: headers.iter().position(|h| h == "name"),
+
+            label: headers.iter()
+                .position(|h| h == "label")
+                .ok_or_else(|| CustomError::MissingColumn("label".to_string()))?,
+
+            type_id: headers.iter()
+                .position(|h| h == "type_id")
+                .ok_or_else(|| CustomError::MissingColumn("type_id".to_string()))?,
+
+            input_type_id: headers.iter()
+                .position(|h| h == "input_type_id")
+                .ok_or_else(|| CustomError::MissingColumn("input_type_id".to_string()))?,
+
+            options: headers.iter()
+                .position(|h| h == "options")
+                .ok_or_else(|| CustomError::MissingColumn("options".to_string()))?,
+
+            optional: OPTIONAL_COLUMNS.iter()
+                .filter_map(|column| headers.iter().position(|h| h == *column).map(|position| (*column, position)))
+                .collect(),
+        })
+    }
+}
+
+impl FieldSource for CsvSource {
+    fn read_records(&self) -> Result<Vec<FieldRecord>> {
+        let mut reader = Reader::from_path(&self.path)?;
+        let positions = self.get_field_positions(reader.headers()?)?;
+
+        reader.records()
+            .enumerate()
+            .map(|(row_idx, result)| {
+                let record = result?;
+                Ok(FieldRecord {
+                    location: RecordLocation::Row(row_idx),
+                    name: positions.name.map(|position| record[position].to_string()).unwrap_or_default(),
+                    label: record[positions.label].to_string(),
+                    type_id: record[positions.type_id].to_string(),
+                    input_type_id: record[positions.input_type_id].to_string(),
+                    options: record[positions.options].to_string(),
+                    optional: positions.optional.iter()
+                        .map(|(column, position)| (*column, record[*position].to_string()))
+                        .collect(),
+                    cells: HashMap::new(),
+                })
+            })
+            .collect()
+    }
+}
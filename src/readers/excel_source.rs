@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use calamine::{open_workbook_auto, Data, Reader};
+use crate::error::{Result, CustomError, RecordLocation};
+use super::{FieldRecord, FieldSource, OPTIONAL_COLUMNS};
+
+// Same headers as the CSV file, in the order they are copied into a `FieldRecord`
+const COLUMNS: [&str; 5] = ["name", "label", "type_id", "input_type_id", "options"];
+
+/// Reads field definitions from one sheet of an Excel or OpenDocument workbook.
+/// The first row of the sheet's used range holds the column headers.
+pub struct ExcelSource {
+    path: String,
+    sheet: Option<String>,
+}
+
+impl ExcelSource {
+    pub fn new(path: &str, sheet: Option<&str>) -> Self {
+        ExcelSource { path: path.to_string(), sheet: sheet.map(str::to_string) }
+    }
+
+    fn invalid(&self, message: String) -> CustomError {
+        CustomError::InvalidSourceFile(self.path.clone(), message)
+    }
+
+    // An exact sheet name wins over a 1-based position, so a sheet called "2" can still be picked
+    fn select_sheet(&self, names: &[String]) -> Result<String> {
+        let selected = match &self.sheet {
+            None => names.first(),
+            Some(wanted) => names.iter()
+                .find(|name| *name == wanted)
+                .or_else(|| wanted.parse::<usize>().ok()
+                    .filter(|position| *position > 0)
+                    .and_then(|position| names.get(position - 1))),
+        };
+
+        selected.cloned().ok_or_else(|| self.invalid(format!(
+            "sheet '{}' not found. Available sheets: {}",
+            self.sheet.as_deref().unwrap_or_default(),
+            names.join(", ")
+        )))
+    }
+}
+
+// 0 -> A, 25 -> Z, 26 -> AA
+fn column_letters(mut index: u32) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push((b'A' + (index % 26) as u8) as char);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+// Sheet names with spaces or punctuation are quoted in cell references, as Excel does
+fn cell_reference(sheet: &str, column: u32, row: u32) -> String {
+    if sheet.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        format!("{}!{}{}", sheet, column_letters(column), row)
+    } else {
+        format!("'{}'!{}{}", sheet.replace('\'', "''"), column_letters(column), row)
+    }
+}
+
+impl FieldSource for ExcelSource {
+    fn read_records(&self) -> Result<Vec<FieldRecord>> {
+        let mut workbook = open_workbook_auto(&self.path).map_err(|e| self.invalid(e.to_string()))?;
+        let sheet = self.select_sheet(&workbook.sheet_names())?;
+        let range = workbook.worksheet_range(&sheet)
+            .map_err(|e| self.invalid(format!("sheet '{}': {}", sheet, e)))?;
+
+        // Used ranges don't have to start at A1
+        let (first_row, first_column) = range.start().unwrap_or((0, 0));
+        let mut rows = range.rows();
+
+        let headers: Vec<String> = rows.next()
+            .map(|row| row.iter().map(|cell| cell.to_string().trim().to_string()).collect())
+            .unwrap_or_default();
+        let positions = COLUMNS.iter()
+            .map(|column| match headers.iter().position(|header| header == column) {
+                Some(position) => Ok(Some(position)),
+                // Names can be generated from labels, so the column may be left out
+                None if *column == "name" => Ok(None),
+                None => Err(CustomError::MissingColumn(column.to_string())),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let optional_positions: Vec<_> = OPTIONAL_COLUMNS.iter()
+            .filter_map(|column| headers.iter().position(|header| header == column).map(|position| (*column, position)))
+            .collect();
+
+        let records = rows.enumerate()
+            // Formatted but empty rows are part of the used range, skip them like blank CSV lines
+            .filter(|(_, row)| row.iter().any(|cell| *cell != Data::Empty))
+            .map(|(index, row)| {
+                // +2 because: +1 for header row, +1 because sheet rows start at 1
+                let sheet_row = first_row + index as u32 + 2;
+                let cell_value = |position: usize| row.get(position)
+                    .map(|cell| cell.to_string())
+                    .unwrap_or_default();
+                let value = |column: usize| positions[column].map(cell_value).unwrap_or_default();
+                let cells = COLUMNS.iter()
+                    .zip(&positions)
+                    .filter_map(|(column, position)| position.as_ref().map(|position| (column, position)))
+                    .chain(optional_positions.iter().map(|(column, position)| (column, position)))
+                    .map(|(column, position)| (*column, cell_reference(&sheet, first_column + *position as u32, sheet_row)))
+                    .collect::<HashMap<_, _>>();
+
+                FieldRecord {
+                    location: RecordLocation::Sheet { sheet: sheet.clone(), row: sheet_row },
+                    name: value(0),
+                    label: value(1),
+                    type_id: value(2),
+                    input_type_id: value(3),
+                    options: value(4),
+                    optional: optional_positions.iter()
+                        .map(|(column, position)| (*column, cell_value(*position)))
+                        .collect(),
+                    cells,
+                }
+            })
+            .collect();
+
+        Ok(records)
+    }
+}
@@ -0,0 +1,410 @@
+
+    mod csv_source;
+    mod excel_source;
+    mod structured_source;
+
+    use std::collections::HashMap;
+    use std::collections::hash_map::Entry;
+    use std::path::Path;
+    use log::warn;
+    use crate::models::entity::Entity;
+    use crate::models::field::{Field, FieldFlags};
+    use crate::models::field_type::{FieldType, InputType};
+    use crate::models::option_list::{OptionList, OptionOrder};
+    use crate::config::Config;
+    use crate::lint::{LintRules, Severity};
+    use crate::naming::NameGenerator;
+    use crate::error::{Result, CustomError, FieldError, FieldErrorKind, RecordLocation};
+    use crate::models::validation_report::ValidationReport;
+    use csv_source::CsvSource;
+    use excel_source::ExcelSource;
+    use structured_source::{StructuredSource, Format};
+
+    /// Columns that may be left out of the input file, missing or empty values keep the defaults.
+    pub const OPTIONAL_COLUMNS: [&str; 7] = [
+        "usage", "searchable", "user_searchable", "calendar_searchable", "copytochild", "copytochildonupdate",
+        "option_order",
+    ];
+
+    /// One field definition exactly as written in the input file, before validation.
+    #[derive(Debug)]
+    pub struct FieldRecord {
+        pub location: RecordLocation,
+        pub name: String,
+        pub label: String,
+        pub type_id: String,
+        pub input_type_id: String,
+        pub options: String,
+        // Values of the optional columns present in the file
+        pub optional: HashMap<&'static str, String>,
+        // Cell reference of each column, only known for workbook sheets
+        pub cells: HashMap<&'static str, String>,
+    }
+
+    impl FieldRecord {
+        pub fn optional_value(&self, column: &str) -> &str {
+            self.optional.get(column).map(String::as_str).unwrap_or_default()
+        }
+    }
+
+    /// A file format field definitions can be read from. Sources only extract the
+    /// raw values, validation is shared by all of them in `FieldReader`.
+    pub trait FieldSource {
+        fn read_records(&self) -> Result<Vec<FieldRecord>>;
+    }
+
+    /// Picks the source matching the file extension, falling back to CSV.
+    pub fn open_source(config: &Config) -> Box<dyn FieldSource> {
+        let path = config.source_file_name.as_str();
+        let extension = Path::new(path).extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("json") => Box::new(StructuredSource::new(path, Format::Json)),
+            Some("yaml") | Some("yml") => Box::new(StructuredSource::new(path, Format::Yaml)),
+            Some("xlsx") | Some("xlsm") | Some("xls") | Some("ods") =>
+                Box::new(ExcelSource::new(path, config.source_sheet.as_deref())),
+            _ => Box::new(CsvSource::new(path)),
+        }
+    }
+    
+    // A validated name or label and where it was written, for the checks across rows
+    struct Written {
+        location: RecordLocation,
+        value: String,
+        cell: Option<String>,
+    }
+
+    pub struct FieldReader;
+    
+    impl FieldReader {
+        pub fn new() -> Self {
+            FieldReader
+        }
+    
+        #[allow(dead_code)]
+        fn validate_required_field(&self, value: &str, field_name: &str, location: &RecordLocation) -> Result<String> {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                return Err(self.field_error(location, field_name, value,
+                    FieldErrorKind::RequiredFieldEmpty(field_name.to_string())
+                ));
+            }
+            Ok(trimmed.to_string())
+        }
+    
+        fn field_error(&self, location: &RecordLocation, column: &str, value: &str, error: FieldErrorKind) -> CustomError {
+            CustomError::FieldError(Box::new(FieldError {
+                location: location.clone(),
+                column: column.to_string(),
+                cell: None,
+                value: value.to_string(),
+                error,
+            }))
+        }
+    
+        fn validate_field_name(&self, name: &str, location: &RecordLocation) -> Result<String> {
+            let trimmed = name.trim();
+            
+            if trimmed.is_empty() {
+                return Err(self.field_error(location, "name", name,
+                    FieldErrorKind::RequiredFieldEmpty("name".to_string())
+                ));
+            }
+            
+            if !trimmed.chars().all(|c| c.is_alphanumeric()) {
+                return Err(self.field_error(location, "name", name,
+                    FieldErrorKind::InvalidFieldName(trimmed.to_string())
+                ));
+            }
+            
+            Ok(trimmed.to_string())
+        }
+    
+        fn validate_label(&self, label: &str, location: &RecordLocation) -> Result<String> {
+            let trimmed = label.trim();
+            
+            if trimmed.is_empty() {
+                return Err(self.field_error(location, "label", label,
+                    FieldErrorKind::RequiredFieldEmpty("label".to_string())
+                ));
+            }
+            
+            if label.trim() == " " {
+                return Err(self.field_error(location, "label", label,
+                    FieldErrorKind::InvalidLabel("Label cannot be a single space".to_string())
+                ));
+            }
+            
+            Ok(trimmed.to_string())
+        }
+    
+        fn generate_name(&self, generator: &mut NameGenerator, label: &str, location: &RecordLocation) -> Result<String> {
+            generator.generate(label).map_err(|reason| self.field_error(location, "name", "",
+                FieldErrorKind::NameNotGenerated(label.to_string(), reason)
+            ))
+        }
+    
+        fn validate_type_id(&self, value: &str, location: &RecordLocation) -> Result<FieldType> {
+            if value.trim().is_empty() {
+                return Err(self.field_error(location, "type_id", value, FieldErrorKind::RequiredFieldEmpty("type_id".to_string())));
+            }
+
+            FieldType::parse(value).ok_or_else(|| self.field_error(location, "type_id", value,
+                FieldErrorKind::InvalidTypeId(value.trim().to_string(), FieldType::suggest(value))
+            ))
+        }
+    
+        fn validate_input_type(&self, value: &str, field_type: FieldType, location: &RecordLocation) -> Result<InputType> {
+            if value.trim().is_empty() {
+                return Err(self.field_error(location, "input_type_id", value, FieldErrorKind::RequiredFieldEmpty("input_type_id".to_string())));
+            }
+
+            InputType::parse(field_type, value).ok_or_else(|| self.field_error(location, "input_type_id", value,
+                FieldErrorKind::InvalidInputType(field_type, InputType::suggest(field_type, value))
+            ))
+        }
+    
+        fn validate_option_order(&self, value: &str, location: &RecordLocation) -> Result<OptionOrder> {
+            OptionOrder::parse(value).ok_or_else(|| self.field_error(location, "option_order", value,
+                FieldErrorKind::InvalidOptionOrder(value.trim().to_string())
+            ))
+        }
+    
+        fn validate_options(
+            &self,
+            options: &str,
+            order: OptionOrder,
+            field_type: FieldType,
+            location: &RecordLocation,
+        ) -> Result<OptionList> {
+            let invalid = |error| self.field_error(location, "options", options, error);
+
+            let mut list = OptionList::parse(options);
+
+            // Single/Multiple Selection fields require options, other field types don't need options validation
+            if field_type.requires_options() && list.is_empty() {
+                return Err(invalid(FieldErrorKind::MissingOptions(
+                    "Selection fields require at least one option".to_string()
+                )));
+            }
+
+            let duplicates = list.duplicates();
+            if !duplicates.is_empty() {
+                return Err(invalid(FieldErrorKind::DuplicateOptions(duplicates)));
+            }
+
+            list.sort(order);
+            Ok(list)
+        }
+    
+        fn validate_usage(&self, value: &str, field_type: Option<FieldType>, default: Entity, location: &RecordLocation) -> Result<Entity> {
+            let usage = if value.trim().is_empty() {
+                default
+            } else {
+                Entity::parse(value).ok_or_else(|| self.field_error(location, "usage", value,
+                    FieldErrorKind::InvalidUsage(value.trim().to_string(), Entity::suggest(value))
+                ))?
+            };
+
+            // The field type can only be checked against the entity once it is known to be valid
+            match field_type {
+                Some(field_type) if !usage.allows(field_type) => Err(self.field_error(location, "usage", value,
+                    FieldErrorKind::FieldTypeNotAllowed(field_type, usage)
+                )),
+                _ => Ok(usage),
+            }
+        }
+    
+        fn validate_flag(&self, column: &str, value: &str, location: &RecordLocation) -> Result<bool> {
+            match value.trim().to_lowercase().as_str() {
+                "" => Ok(true),
+                "true" | "yes" | "y" | "1" => Ok(true),
+                "false" | "no" | "n" | "0" => Ok(false),
+                _ => Err(self.field_error(location, column, value, FieldErrorKind::InvalidFlag(column.to_string()))),
+            }
+        }
+    
+        // Every flag is checked so all invalid ones end up in the report
+        fn validate_flags(&self, record: &FieldRecord, report: &mut ValidationReport) -> Result<Option<FieldFlags>> {
+            let mut flag = |column: &str| self.collect(
+                self.validate_flag(column, record.optional_value(column), &record.location), record, report
+            );
+
+            let flags = (
+                flag("searchable")?,
+                flag("user_searchable")?,
+                flag("calendar_searchable")?,
+                flag("copytochild")?,
+                flag("copytochildonupdate")?,
+            );
+
+            match flags {
+                (Some(searchable), Some(user_searchable), Some(calendar_searchable), Some(copy_to_child), Some(copy_to_child_on_update)) =>
+                    Ok(Some(FieldFlags { searchable, user_searchable, calendar_searchable, copy_to_child, copy_to_child_on_update })),
+                _ => Ok(None),
+            }
+        }
+    
+        // Moves a row-level validation error into the report so the remaining rows still get checked
+        fn collect<T>(&self, result: Result<T>, record: &FieldRecord, report: &mut ValidationReport) -> Result<Option<T>> {
+            match result {
+                Ok(value) => Ok(Some(value)),
+                Err(CustomError::FieldError(mut error)) => {
+                    error.cell = record.cells.get(error.column.as_str()).cloned();
+                    report.add(*error);
+                    Ok(None)
+                },
+                Err(other) => Err(other),
+            }
+        }
+    
+        // Reports every value an earlier row already used, ignoring case, against the later row
+        fn validate_unique(
+            &self,
+            column: &str,
+            written: &[Written],
+            report: &mut ValidationReport,
+            error: fn(&Written, &Written) -> FieldErrorKind,
+        ) {
+            let mut first_seen: HashMap<String, &Written> = HashMap::new();
+            for current in written {
+                match first_seen.entry(current.value.to_lowercase()) {
+                    Entry::Occupied(first) => report.add(FieldError {
+                        location: current.location.clone(),
+                        column: column.to_string(),
+                        cell: current.cell.clone(),
+                        value: current.value.clone(),
+                        error: error(first.get(), current),
+                    }),
+                    Entry::Vacant(slot) => {
+                        slot.insert(current);
+                    },
+                }
+            }
+        }
+    
+        // Lint findings are reported against the valid name or label they concern
+        fn lint(&self, rules: &LintRules, record: &FieldRecord, name: &str, label: &str, report: &mut ValidationReport) {
+            for finding in rules.check(name, label) {
+                let error = FieldError {
+                    location: record.location.clone(),
+                    column: finding.column.to_string(),
+                    cell: record.cells.get(finding.column).cloned(),
+                    value: if finding.column == "name" { name.to_string() } else { label.to_string() },
+                    error: FieldErrorKind::LintViolation(finding.rule, finding.message),
+                };
+                match finding.severity {
+                    Severity::Error => report.add(error),
+                    Severity::Warning => report.add_warning(error),
+                }
+            }
+        }
+    
+        pub fn read_fields(&self, config: &Config) -> Result<Vec<Field>> {
+            let rules = match &config.lint_rules_file {
+                Some(path) => LintRules::load(path)?,
+                None => LintRules::default(),
+            };
+            let mut fields = Vec::new();
+            let mut names = Vec::new();
+            let mut labels = Vec::new();
+            let mut report = ValidationReport::new();
+            let records = open_source(config).read_records()?;
+
+            // Generated names follow the naming rules unless configured otherwise
+            let prefix = config.name_prefix.clone()
+                .or_else(|| rules.name_prefix.as_ref().map(|rule| rule.value.clone()))
+                .unwrap_or_default();
+            let max_length = rules.name_max_length.as_ref()
+                .map_or(config.name_max_length, |rule| rule.value.min(config.name_max_length));
+            let mut generator = NameGenerator::new(
+                &prefix,
+                max_length,
+                records.iter().map(|record| record.name.trim()).filter(|name| !name.is_empty()),
+            );
+    
+            for record in records {
+                let location = &record.location;
+                
+                // Validate type_id first as other validations depend on it
+                let field_type = self.collect(self.validate_type_id(&record.type_id, location), &record, &mut report)?;
+                let label = self.collect(self.validate_label(&record.label, location), &record, &mut report)?;
+                // A blank name is derived from the label, which has to be valid for that
+                let generated_name = record.name.trim().is_empty();
+                let name = match &label {
+                    Some(label) if generated_name =>
+                        self.collect(self.generate_name(&mut generator, label, location), &record, &mut report)?,
+                    None if generated_name => None,
+                    _ => self.collect(self.validate_field_name(&record.name, location), &record, &mut report)?,
+                };
+                let usage = self.collect(self.validate_usage(record.optional_value("usage"), field_type, config.default_usage, location), &record, &mut report)?;
+                let flags = self.validate_flags(&record, &mut report)?;
+
+                if let (Some(name), Some(label)) = (&name, &label) {
+                    self.lint(&rules, &record, name, label, &mut report);
+                }
+
+                // Kept for the checks across rows, even when the row has other errors
+                if let Some(name) = &name {
+                    names.push(Written { location: location.clone(), value: name.clone(), cell: record.cells.get("name").cloned() });
+                }
+                if let Some(label) = &label {
+                    labels.push(Written { location: location.clone(), value: label.clone(), cell: record.cells.get("label").cloned() });
+                }
+
+                // Input type and options can only be checked against a valid type_id
+                let order = self.collect(self.validate_option_order(record.optional_value("option_order"), location), &record, &mut report)?;
+                let (input_type, options) = match field_type {
+                    Some(field_type) => {
+                        let input_type = self.collect(self.validate_input_type(&record.input_type_id, field_type, location), &record, &mut report)?;
+                        let options = self.collect(
+                            self.validate_options(&record.options, order.unwrap_or_default(), field_type, location),
+                            &record,
+                            &mut report
+                        )?;
+                        (input_type, options)
+                    },
+                    None => (None, None),
+                };
+
+                if let (Some(name), Some(label), Some(field_type), Some(input_type), Some(options), Some(usage), Some(flags)) =
+                    (name, label, field_type, input_type, options, usage, flags)
+                {
+                    let mut field = Field::new(name, label, field_type, input_type, options, usage, flags);
+                    field.generated_name = generated_name;
+                    fields.push(field);
+                }
+            }
+
+            self.validate_unique("name", &names, &mut report, |first, current| {
+                if first.value == current.value {
+                    FieldErrorKind::DuplicateName(first.location.clone())
+                } else {
+                    FieldErrorKind::NameCollision(first.value.clone(), first.location.clone())
+                }
+            });
+            self.validate_unique("label", &labels, &mut report, |first, _| {
+                FieldErrorKind::DuplicateLabel(first.location.clone())
+            });
+
+            if !report.is_empty() {
+                if let Some(path) = &config.validation_report_file {
+                    report.write(path)?;
+                }
+                return Err(CustomError::ValidationFailed(report));
+            }
+
+            for warning in &report.warnings {
+                warn!("{}: {} = '{}': {}", warning.location, warning.column, warning.value, warning.error);
+            }
+            if !report.warnings.is_empty() {
+                if let Some(path) = &config.validation_report_file {
+                    report.write(path)?;
+                }
+            }
+    
+            Ok(fields)
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::fs;
+use log::warn;
+use serde_json::{Map, Value};
+use crate::error::{Result, CustomError, IOErrorKind, RecordLocation};
+use super::{FieldRecord, FieldSource, OPTIONAL_COLUMNS};
+
+// Keys understood in a field definition, `type` and `input_type` read better than the CSV headers
+const KNOWN_KEYS: [&str; 7] = ["name", "label", "type_id", "type", "input_type_id", "input_type", "options"];
+
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    Json,
+    Yaml,
+}
+
+/// Reads field definitions from a JSON or YAML document, either a list of
+/// fields or an object with a `fields` list. Both formats are parsed into the
+/// same value tree so they share the extraction below.
+pub struct StructuredSource {
+    path: String,
+    format: Format,
+}
+
+impl StructuredSource {
+    pub fn new(path: &str, format: Format) -> Self {
+        StructuredSource { path: path.to_string(), format }
+    }
+
+    fn invalid(&self, message: String) -> CustomError {
+        CustomError::InvalidSourceFile(self.path.clone(), message)
+    }
+
+    fn parse(&self) -> Result<Value> {
+        let text = fs::read_to_string(&self.path).map_err(|e|
+            CustomError::IOError(IOErrorKind::ReadFile(format!("{}: {}", self.path, e)))
+        )?;
+
+        match self.format {
+            Format::Json => serde_json::from_str(&text).map_err(|e| self.invalid(e.to_string())),
+            Format::Yaml => serde_yaml::from_str(&text).map_err(|e| self.invalid(e.to_string())),
+        }
+    }
+
+    // Scalars are accepted in any form, so `type_id: 2` and `type_id: "2"` are the same
+    fn text(&self, entry: &Map<String, Value>, keys: &[&str], location: &RecordLocation) -> Result<String> {
+        let Some(value) = keys.iter().find_map(|key| entry.get(*key)) else {
+            return Ok(String::new());
+        };
+
+        match value {
+            Value::Null => Ok(String::new()),
+            Value::String(text) => Ok(text.clone()),
+            Value::Number(number) => Ok(number.to_string()),
+            Value::Bool(flag) => Ok(flag.to_string()),
+            Value::Array(_) | Value::Object(_) => Err(self.invalid(format!(
+                "{}: '{}' must be a single value", location, keys[0]
+            ))),
+        }
+    }
+
+    // Options may be written as a list instead of a comma-separated string. Halo receives
+    // them joined with commas, so an item can't contain one
+    fn options(&self, entry: &Map<String, Value>, location: &RecordLocation) -> Result<String> {
+        match entry.get("options") {
+            Some(Value::Array(items)) => {
+                let options = items.iter()
+                    .map(|item| match item {
+                        Value::String(text) if text.contains(',') => Err(self.invalid(format!(
+                            "{}: option '{}' contains a comma, which would split it into separate options", location, text
+                        ))),
+                        Value::String(text) => Ok(text.clone()),
+                        Value::Number(number) => Ok(number.to_string()),
+                        Value::Bool(flag) => Ok(flag.to_string()),
+                        _ => Err(self.invalid(format!("{}: options must be a list of values", location))),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(options.join(","))
+            },
+            _ => self.text(entry, &["options"], location),
+        }
+    }
+}
+
+impl FieldSource for StructuredSource {
+    fn read_records(&self) -> Result<Vec<FieldRecord>> {
+        let document = self.parse()?;
+        let entries = match &document {
+            Value::Array(entries) => entries,
+            Value::Object(object) => match object.get("fields") {
+                Some(Value::Array(entries)) => entries,
+                _ => return Err(self.invalid("expected a 'fields' list".to_string())),
+            },
+            _ => return Err(self.invalid("expected a list of fields".to_string())),
+        };
+
+        entries.iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let location = RecordLocation::Entry(index);
+                let Value::Object(entry) = entry else {
+                    return Err(self.invalid(format!("{}: expected a field definition with named values", location)));
+                };
+
+                let is_known = |key: &str| KNOWN_KEYS.contains(&key) || OPTIONAL_COLUMNS.contains(&key);
+                for key in entry.keys().filter(|key| !is_known(key)) {
+                    warn!("{}: ignoring unknown key '{}'", location, key);
+                }
+
+                let mut optional = HashMap::new();
+                for column in OPTIONAL_COLUMNS.iter().filter(|column| entry.contains_key(**column)) {
+                    optional.insert(*column, self.text(entry, &[column], &location)?);
+                }
+
+                Ok(FieldRecord {
+                    name: self.text(entry, &["name"], &location)?,
+                    label: self.text(entry, &["label"], &location)?,
+                    type_id: self.text(entry, &["type_id", "type"], &location)?,
+                    input_type_id: self.text(entry, &["input_type_id", "input_type"], &location)?,
+                    options: self.options(entry, &location)?,
+                    optional,
+                    cells: HashMap::new(),
+                    location,
+                })
+            })
+            .collect()
+    }
+}
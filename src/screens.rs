@@ -1,17 +1,43 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use crate::models::field::Field;
-use crate::error::Result;
-use log::{info, error};
+use crate::error::{Result, CustomError};
+use log::{info, warn, error};
 use colored::*;
 use crate::api::field_client::FieldClient;
-use crate::models::import_result::ImportResults;
+use crate::models::import_result::{ImportResults, id_suffix};
+use crate::models::halo_field::HaloField;
+use crate::models::preflight::PreflightStatus;
+use crate::models::run_journal::RunJournal;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RunMode {
     Import,
+    Validate,
     Debug,
+    RetryFailed,
+    Plan,
+    Apply,
+    Export,
+    Clone,
     Quit,
 }
 
+impl RunMode {
+    pub fn title(&self) -> &'static str {
+        match self {
+            RunMode::Import => "Import",
+            RunMode::Validate => "Validation",
+            RunMode::Debug => "Debug mode",
+            RunMode::RetryFailed => "Retry of failed fields",
+            RunMode::Plan => "Sync plan",
+            RunMode::Apply => "Sync apply",
+            RunMode::Export => "Export",
+            RunMode::Clone => "Clone",
+            RunMode::Quit => "Quit",
+        }
+    }
+}
+
 pub enum DebugAction {
     Process,
     Skip,
@@ -20,11 +46,133 @@ pub enum DebugAction {
 
 pub struct ScreenManager {
     fields: Vec<Field>,
+    statuses: Vec<PreflightStatus>,
+    assume_yes: bool,
 }
 
 impl ScreenManager {
-    pub fn new(fields: Vec<Field>) -> Self {
-        Self { fields }
+    pub fn new(fields: Vec<Field>, assume_yes: bool) -> Self {
+        let statuses = vec![PreflightStatus::New; fields.len()];
+        Self { fields, statuses, assume_yes }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    pub fn confirm(&self, prompt: &str) -> Result<bool> {
+        if self.assume_yes {
+            info!("{} yes (--yes)", prompt);
+            return Ok(true);
+        }
+        if !io::stdin().is_terminal() {
+            return Err(CustomError::NotInteractive(format!(
+                "'{}' needs an answer, pass --yes to confirm it when stdin is not a terminal",
+                prompt
+            )));
+        }
+
+        Self::ask(prompt)
+    }
+
+    fn ask(prompt: &str) -> Result<bool> {
+        print!("\n{} {}", prompt.bright_white().bold(), "(y/n): ".bright_white().bold());
+        io::stdout().flush()?;
+
+        let input = Self::read_answer()?;
+
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => Ok(true),
+            "n" | "no" => Ok(false),
+            _ => {
+                error!("{}", "Invalid selection. Please enter y or n.".bright_red());
+                Self::ask(prompt)
+            }
+        }
+    }
+
+    /// Reads one line of input, failing at the end of stdin so that a prompt
+    /// asked again after an invalid answer cannot loop forever.
+    fn read_answer() -> Result<String> {
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Err(CustomError::NotInteractive("stdin was closed before an answer was given".to_string()));
+        }
+        Ok(input)
+    }
+
+    /// Lists the fields a sync would delete and asks for confirmation, which `--yes`
+    /// does not give. Without a terminal only `--delete-orphans` allows the deletion.
+    pub fn confirm_deletions(&self, fields: &[&HaloField], delete_orphans: bool) -> Result<bool> {
+        println!("\n{}", "Fields to Delete:".bright_red().bold());
+        println!("{}",   "=".repeat(80).bright_blue());
+        for field in fields {
+            println!("• {} ({}, id {}, {})",
+                field.label.bright_yellow(),
+                field.name,
+                field.id,
+                field.entity().map(|entity| entity.display_name()).unwrap_or("Other")
+            );
+        }
+        println!("{}", "=".repeat(80).bright_blue());
+
+        if delete_orphans {
+            info!("Delete {} field(s)? yes (--delete-orphans)", fields.len());
+            return Ok(true);
+        }
+        if self.assume_yes || !io::stdin().is_terminal() {
+            warn!("Orphaned fields are kept, pass --delete-orphans to delete them without a prompt");
+            return Ok(false);
+        }
+
+        Self::ask(&format!("Delete these {} field(s) from the instance?", fields.len()))
+    }
+
+    /// Matches every field against the fields already on the instance so that
+    /// existing and conflicting fields are skipped instead of being sent again.
+    pub fn preflight(&mut self, existing: &[HaloField]) {
+        self.statuses = self.fields.iter()
+            .map(|field| PreflightStatus::check(field, existing))
+            .collect();
+    }
+
+    pub fn show_preflight_summary(&self) {
+        let count = |predicate: fn(&PreflightStatus) -> bool| {
+            self.statuses.iter().filter(|status| predicate(status)).count()
+        };
+
+        println!("\n{}", "Pre-flight Check:".bright_blue().bold());
+        println!("{}",   "=".repeat(80).bright_blue());
+
+        println!("• New fields: {}", 
+            count(|s| matches!(s, PreflightStatus::New)).to_string().bright_green()
+        );
+        println!("• Already present (will be skipped): {}", 
+            count(|s| matches!(s, PreflightStatus::AlreadyPresent(_))).to_string().bright_cyan()
+        );
+        println!("• Conflicting (will be skipped): {}", 
+            count(|s| matches!(s, PreflightStatus::Conflict { .. })).to_string().bright_red()
+        );
+
+        for (field, status) in self.fields.iter().zip(&self.statuses) {
+            if let PreflightStatus::Conflict { .. } = status {
+                println!("  - {} ({}): {}", 
+                    field.label.bright_yellow(),
+                    field.name,
+                    status.skip_reason().unwrap_or_default().bright_red()
+                );
+            }
+        }
+
+        let generated: Vec<_> = self.fields.iter().filter(|field| field.generated_name).collect();
+        if !generated.is_empty() {
+            println!("• Names generated from labels: {}", generated.len().to_string().bright_cyan());
+            for field in generated {
+                println!("  - {} → {}", field.label.bright_yellow(), field.name.bright_green());
+            }
+        }
+
+        println!("{}\n", "=".repeat(80).bright_blue());
     }
 
     pub fn show_initial_stats(&self, token_type: &str) -> Result<()> {
@@ -48,7 +196,7 @@ impl ScreenManager {
         Ok(())
     }
 
-    pub fn get_run_mode(&self) -> Result<RunMode> {
+    pub fn get_run_mode() -> Result<RunMode> {
         println!("{}", "\nAvailable Operations:".bright_blue().bold());
         println!("{}", "=".repeat(80).bright_blue());
         
@@ -64,14 +212,38 @@ impl ScreenManager {
         
         println!("{}. {}", 
             "3".bright_yellow().bold(), 
+            "Retry failed fields from the last run".bright_green()
+        );
+        
+        println!("{}. {}", 
+            "4".bright_yellow().bold(), 
+            "Plan sync (show changes only)".bright_cyan()
+        );
+        
+        println!("{}. {}", 
+            "5".bright_yellow().bold(), 
+            "Apply sync".bright_green()
+        );
+        
+        println!("{}. {}", 
+            "6".bright_yellow().bold(), 
+            "Export fields from Halo to CSV".bright_cyan()
+        );
+        
+        println!("{}. {}", 
+            "7".bright_yellow().bold(), 
+            "Clone fields from another instance".bright_cyan()
+        );
+        
+        println!("{}. {}", 
+            "8".bright_yellow().bold(), 
             "Quit program".bright_red()
         );
         
-        print!("\n{}", "Enter your choice (1-3): ".bright_white().bold());
+        print!("\n{}", "Enter your choice (1-8): ".bright_white().bold());
         io::stdout().flush()?;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = Self::read_answer()?;
 
         match input.trim() {
             "1" => {
@@ -83,30 +255,91 @@ impl ScreenManager {
                 Ok(RunMode::Debug)
             },
             "3" => {
+                println!("\n{}", "Selected: Retry failed fields".bright_green());
+                Ok(RunMode::RetryFailed)
+            },
+            "4" => {
+                println!("\n{}", "Selected: Plan sync".bright_cyan());
+                Ok(RunMode::Plan)
+            },
+            "5" => {
+                println!("\n{}", "Selected: Apply sync".bright_green());
+                Ok(RunMode::Apply)
+            },
+            "6" => {
+                println!("\n{}", "Selected: Export fields".bright_cyan());
+                Ok(RunMode::Export)
+            },
+            "7" => {
+                println!("\n{}", "Selected: Clone fields".bright_cyan());
+                Ok(RunMode::Clone)
+            },
+            "8" => {
                 println!("\n{}", "Selected: Quit program".bright_red());
                 Ok(RunMode::Quit)
             },
             _ => {
                 error!("{}", "Invalid selection. Please try again.".bright_red());
-                self.get_run_mode()
+                Self::get_run_mode()
             }
         }
     }
 
+    /// Gives the user a chance to fix rows in the failed fields file before it is read.
+    pub fn prompt_failed_fixes(path: &str, assume_yes: bool) -> Result<()> {
+        println!("\n{}", "Retry Failed Fields:".bright_blue().bold());
+        println!("{}",   "=".repeat(80).bright_blue());
+        println!("• Failed fields file: {}", path.bright_yellow());
+        println!("• Every row left in the file is retried, the error column is ignored");
+        println!("• Fix or remove rows in the file now if needed");
+        println!("{}", "=".repeat(80).bright_blue());
+
+        if assume_yes {
+            return Ok(());
+        }
+
+        print!("\n{}", "Press Enter to continue...".bright_white().bold());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(())
+    }
+
+    pub fn get_name_pattern() -> Result<String> {
+        println!("\n{}", "Filter fields by name (use * and ? as wildcards, leave empty for all):".bright_blue().bold());
+        print!("{}", "Name pattern: ".bright_white().bold());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        Ok(input.trim().to_string())
+    }
+
     pub async fn process_all_fields(
         &self, 
         field_client: &FieldClient,
+        journal: Option<RunJournal>,
     ) -> Result<ImportResults> {
-        let mut results = ImportResults::new();
+        let mut results = ImportResults::with_journal(journal);
         
-        for field in &self.fields {
-            match field_client.create_field(field).await {
-                Ok(_) => {
-                    results.add_success(field.label.clone());
-                    info!("✓ Field processed successfully: {}", field.label);
+        for (field, status) in self.fields.iter().zip(&self.statuses) {
+            if let Some(reason) = status.skip_reason() {
+                info!("↷ Field skipped: {} ({})", field.label, reason);
+                results.add_skipped(field.label.clone(), Some(field.usage), status.existing_id(), reason);
+                continue;
+            }
+
+            let outcome = field_client.create_field(field).await;
+            match outcome.result {
+                Ok(created) => {
+                    let halo_id = created.map(|created| created.id);
+                    results.add_success(field.label.clone(), Some(field.usage), halo_id, outcome.attempts, outcome.elapsed, outcome.payload);
+                    info!("✓ Field processed successfully: {}{}", field.label, id_suffix(halo_id));
                 },
                 Err(e) => {
-                    results.add_failure(field.label.clone(), e.to_string());
+                    results.add_failure(field.label.clone(), Some(field.usage), e.to_string(), outcome.attempts, outcome.elapsed, outcome.payload);
                     error!("✗ Field processing failed: {}", e);
                 }
             }
@@ -118,30 +351,40 @@ impl ScreenManager {
     pub async fn debug_mode(
         &self, 
         field_client: &FieldClient,
+        journal: Option<RunJournal>,
     ) -> Result<ImportResults> {
         info!("\nEntering Debug Mode");
         info!("This mode will process fields one at a time\n");
 
-        let mut results = ImportResults::new();
+        let mut results = ImportResults::with_journal(journal);
+
+        for (index, (field, status)) in self.fields.iter().zip(&self.statuses).enumerate() {
+            if let Some(reason) = status.skip_reason() {
+                info!("Skipping field {} of {}: {} ({})\n", index + 1, self.fields.len(), field.label, reason);
+                results.add_skipped(field.label.clone(), Some(field.usage), status.existing_id(), reason);
+                continue;
+            }
 
-        for (index, field) in self.fields.iter().enumerate() {
             match self.show_field_debug_prompt(index, field)? {
                 DebugAction::Process => {
                     info!("Processing field: {}", field.label);
                     
-                    match field_client.create_field(field).await {
-                        Ok(_) => {
-                            results.add_success(field.label.clone());
-                            info!("✓ Field processed successfully\n");
+                    let outcome = field_client.create_field(field).await;
+                    match outcome.result {
+                        Ok(created) => {
+                            let halo_id = created.map(|created| created.id);
+                            results.add_success(field.label.clone(), Some(field.usage), halo_id, outcome.attempts, outcome.elapsed, outcome.payload);
+                            info!("✓ Field processed successfully{}\n", id_suffix(halo_id));
                         },
                         Err(e) => {
-                            results.add_failure(field.label.clone(), e.to_string());
+                            results.add_failure(field.label.clone(), Some(field.usage), e.to_string(), outcome.attempts, outcome.elapsed, outcome.payload);
                             error!("✗ Field processing failed: {}\n", e);
                         }
                     }
                 },
                 DebugAction::Skip => {
                     info!("Skipping field: {}\n", field.label);
+                    results.add_skipped(field.label.clone(), Some(field.usage), None, "Skipped by user".to_string());
                     continue;
                 },
                 DebugAction::Quit => {
@@ -165,11 +408,12 @@ impl ScreenManager {
         println!("\n{}", "Field Details:".bright_blue().bold());
         println!("• Label: {}", field.label.bright_yellow());
         println!("• Name: {}", field.name.bright_yellow());
-        println!("• Type: {}", field.type_id.to_string().bright_yellow());
-        println!("• Input Type: {}", field.input_type_id.to_string().bright_yellow());
+        println!("• Type: {}", field.field_type.to_string().bright_yellow());
+        println!("• Input Type: {}", field.input_type.to_string().bright_yellow());
+        println!("• Usage: {}", field.usage.to_string().bright_yellow());
         
         if !field.options.is_empty() {
-            println!("• Options: {}", field.options.bright_yellow());
+            println!("• Options: {}", field.options.to_string().bright_yellow());
         }
 
         println!("\n{}", "Available actions:".bright_blue().bold());
@@ -189,8 +433,7 @@ impl ScreenManager {
         print!("\n{}", "Enter your choice (1-3): ".bright_white().bold());
         io::stdout().flush()?;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = Self::read_answer()?;
 
         match input.trim() {
             "1" => Ok(DebugAction::Process),
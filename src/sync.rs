@@ -0,0 +1,378 @@
+use colored::*;
+use log::{info, error};
+use crate::api::field_client::{ApiOutcome, FieldClient};
+use crate::models::entity::Entity;
+use crate::models::field::Field;
+use crate::models::field_type::InputType;
+use crate::models::halo_field::HaloField;
+use crate::models::import_result::{ImportResults, id_suffix};
+
+/// What has to happen to bring a single field on the instance in line with the input file.
+pub enum PlanAction {
+    Create(Field),
+    Update { field: Field, existing: HaloField, changes: Vec<String> },
+    // A different field type or entity can't be changed in place, imports skip these fields too
+    Conflict { field: Field, existing: HaloField, reason: String },
+    NoOp(Field, i64),  // existing field id
+    Orphan(HaloField),
+}
+
+/// The input file treated as desired state, diffed against the fields on the instance.
+pub struct SyncPlan {
+    actions: Vec<PlanAction>,
+    delete_orphans: bool,
+}
+
+impl SyncPlan {
+    pub fn build(fields: &[Field], existing: &[HaloField], delete_orphans: bool) -> Self {
+        let mut actions = Vec::new();
+        let mut matched = vec![false; existing.len()];
+        let usages: Vec<Entity> = fields.iter().map(|field| field.usage).collect();
+
+        for field in fields {
+            match existing.iter().position(|halo_field| halo_field.matches_name(&field.name)) {
+                None => actions.push(PlanAction::Create(field.clone())),
+                Some(index) => {
+                    matched[index] = true;
+                    let existing = existing[index].clone();
+                    if let Some(reason) = Self::conflict(field, &existing) {
+                        actions.push(PlanAction::Conflict { field: field.clone(), existing, reason });
+                        continue;
+                    }
+
+                    let changes = Self::diff(field, &existing);
+                    if changes.is_empty() {
+                        actions.push(PlanAction::NoOp(field.clone(), existing.id));
+                    } else {
+                        actions.push(PlanAction::Update { field: field.clone(), existing, changes });
+                    }
+                }
+            }
+        }
+
+        // Only fields of the entities in the input file are compared, `/fieldinfo`
+        // returns the fields of every entity
+        for (halo_field, was_matched) in existing.iter().zip(matched) {
+            let in_scope = halo_field.entity().is_some_and(|entity| usages.contains(&entity));
+            if !was_matched && in_scope {
+                actions.push(PlanAction::Orphan(halo_field.clone()));
+            }
+        }
+
+        Self { actions, delete_orphans }
+    }
+
+    // Halo keeps the type and entity a field was created with
+    fn conflict(field: &Field, existing: &HaloField) -> Option<String> {
+        if field.field_type.id() != existing.type_id {
+            return Some(format!("type: {} → {}", existing.type_id, field.field_type));
+        }
+        if existing.entity() != Some(field.usage) {
+            let current = existing.entity()
+                .map(|entity| entity.to_string())
+                .unwrap_or_else(|| existing.usage.to_string());
+            return Some(format!("usage: {} → {}", current, field.usage));
+        }
+        None
+    }
+
+    fn diff(field: &Field, existing: &HaloField) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if field.label != existing.label {
+            changes.push(format!("label: '{}' → '{}'", existing.label, field.label));
+        }
+
+        if field.input_type.id() != existing.input_type_id {
+            changes.push(format!("input type: {} → {}", existing.input_type_id, field.input_type));
+        }
+
+        // Only selection fields carry options worth comparing. Halo lists the levels of a tree
+        // dropdown as flat values without their parents, so those can't be compared
+        let is_tree = existing.input_type_id == InputType::TreeDropdown.id() || field.input_type == InputType::TreeDropdown;
+        let options_changed = field.options.names() != existing.options().names();
+        if field.field_type.requires_options() && !is_tree && options_changed {
+            changes.push(format!("options: '{}' → '{}'", existing.options(), field.options));
+        }
+
+        // Flags Halo did not report can't be compared
+        let flags = [
+            ("searchable", existing.searchable, field.flags.searchable),
+            ("user searchable", existing.user_searchable, field.flags.user_searchable),
+            ("calendar searchable", existing.calendar_searchable, field.flags.calendar_searchable),
+            ("copy to child", existing.copytochild, field.flags.copy_to_child),
+            ("copy to child on update", existing.copytochildonupdate, field.flags.copy_to_child_on_update),
+        ];
+        for (flag, current, wanted) in flags {
+            if let Some(current) = current.filter(|current| *current != wanted) {
+                changes.push(format!("{}: {} → {}", flag, current, wanted));
+            }
+        }
+
+        changes
+    }
+
+    fn count(&self, predicate: fn(&PlanAction) -> bool) -> usize {
+        self.actions.iter().filter(|action| predicate(action)).count()
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.actions.iter().any(|action| match action {
+            PlanAction::Create(_) | PlanAction::Update { .. } => true,
+            PlanAction::Orphan(_) => self.delete_orphans,
+            PlanAction::Conflict { .. } | PlanAction::NoOp(..) => false,
+        })
+    }
+
+    pub fn print(&self) {
+        println!("\n{}", "Sync Plan:".bright_blue().bold());
+        println!("{}", "=".repeat(80).bright_blue());
+
+        for action in &self.actions {
+            match action {
+                PlanAction::Create(field) => {
+                    println!("{} {} ({})", "+ create".bright_green().bold(), field.label.bright_yellow(), field.name);
+                },
+                PlanAction::Update { field, changes, .. } => {
+                    println!("{} {} ({})", "~ update".bright_cyan().bold(), field.label.bright_yellow(), field.name);
+                    for change in changes {
+                        println!("    {}", change);
+                    }
+                },
+                PlanAction::Conflict { field, reason, .. } => {
+                    println!("{} {} ({})", "! conflict".bright_red().bold(), field.label.bright_yellow(), field.name);
+                    println!("    {}, this can't be changed in place", reason);
+                },
+                PlanAction::NoOp(..) => {},
+                PlanAction::Orphan(existing) => {
+                    let verb = if self.delete_orphans { "- delete" } else { "? orphan" };
+                    println!("{} {} ({})", verb.bright_red().bold(), existing.label.bright_yellow(), existing.name);
+                },
+            }
+        }
+
+        println!("\n• To create: {}",
+            self.count(|a| matches!(a, PlanAction::Create(_))).to_string().bright_green()
+        );
+        println!("• To update: {}",
+            self.count(|a| matches!(a, PlanAction::Update { .. })).to_string().bright_cyan()
+        );
+        println!("• Conflicting (will be skipped): {}",
+            self.count(|a| matches!(a, PlanAction::Conflict { .. })).to_string().bright_red()
+        );
+        println!("• Unchanged: {}",
+            self.count(|a| matches!(a, PlanAction::NoOp(..))).to_string().bright_yellow()
+        );
+        println!("• Orphaned ({}): {}",
+            if self.delete_orphans { "will be deleted" } else { "report only" },
+            self.count(|a| matches!(a, PlanAction::Orphan(_))).to_string().bright_red()
+        );
+
+        println!("{}\n", "=".repeat(80).bright_blue());
+    }
+
+    /// Fields that applying this plan would delete from the instance.
+    pub fn deletions(&self) -> Vec<&HaloField> {
+        self.actions.iter()
+            .filter_map(|action| match action {
+                PlanAction::Orphan(existing) if self.delete_orphans => Some(existing),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reports orphaned fields only, for when their deletion was not confirmed.
+    pub fn keep_orphans(&mut self) {
+        self.delete_orphans = false;
+    }
+
+    /// Executes exactly the actions of this plan, nothing is re-read from the instance.
+    pub async fn apply(&self, field_client: &FieldClient) -> ImportResults {
+        let mut results = ImportResults::new();
+
+        for action in &self.actions {
+            match action {
+                PlanAction::Create(field) => {
+                    let outcome = field_client.create_field(field).await
+                        .map(|created| created.map(|created| created.id));
+                    Self::record(&mut results, &field.label, Some(field.usage), "created", outcome);
+                },
+                PlanAction::Update { field, existing, .. } => {
+                    let outcome = field_client.update_field(field, existing.id).await.map(|_| Some(existing.id));
+                    Self::record(&mut results, &field.label, Some(field.usage), "updated", outcome);
+                },
+                PlanAction::Conflict { field, existing, reason } => {
+                    let reason = format!("Conflicts with existing field id {} ({})", existing.id, reason);
+                    info!("↷ Field skipped: {} ({})", field.label, reason);
+                    results.add_skipped(field.label.clone(), Some(field.usage), None, reason);
+                },
+                PlanAction::NoOp(field, id) => {
+                    results.add_skipped(field.label.clone(), Some(field.usage), Some(*id), "No changes".to_string());
+                },
+                PlanAction::Orphan(existing) if self.delete_orphans => {
+                    let outcome = field_client.delete_field(existing).await.map(|_| Some(existing.id));
+                    Self::record(&mut results, &existing.label, existing.entity(), "deleted", outcome);
+                },
+                PlanAction::Orphan(existing) => {
+                    results.add_skipped(existing.label.clone(), existing.entity(), Some(existing.id), "Not in input file".to_string());
+                },
+            }
+        }
+
+        results
+    }
+
+    // The outcome carries the Halo id of the field, when known
+    fn record(results: &mut ImportResults, label: &str, usage: Option<Entity>, verb: &str, outcome: ApiOutcome<Option<i64>>) {
+        match outcome.result {
+            Ok(halo_id) => {
+                results.add_success(label.to_string(), usage, halo_id, outcome.attempts, outcome.elapsed, outcome.payload);
+                info!("✓ Field {}: {}{}", verb, label, id_suffix(halo_id));
+            },
+            Err(e) => {
+                results.add_failure(label.to_string(), usage, e.to_string(), outcome.attempts, outcome.elapsed, outcome.payload);
+                error!("✗ Field processing failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::field::FieldFlags;
+    use crate::models::field_type::FieldType;
+    use crate::models::option_list::OptionList;
+
+    fn field(name: &str, field_type: FieldType, input_type: InputType, options: &str, usage: Entity) -> Field {
+        Field::new(
+            name.to_string(),
+            name.to_string(),
+            field_type,
+            input_type,
+            OptionList::parse(options),
+            usage,
+            FieldFlags::default(),
+        )
+    }
+
+    fn text(name: &str, usage: Entity) -> Field {
+        field(name, FieldType::Text, InputType::Anything, "", usage)
+    }
+
+    fn existing(id: i64, name: &str, field_type: FieldType, input_type: InputType, usage: u8, values: &[&str]) -> HaloField {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "label": name.strip_prefix("CF").unwrap_or(name),
+            "type": field_type.id(),
+            "inputtype": input_type.id(),
+            "usage": usage,
+            "values": values.iter().map(|value| serde_json::json!({ "name": value })).collect::<Vec<_>>(),
+        })).unwrap()
+    }
+
+    fn existing_text(id: i64, name: &str, usage: u8) -> HaloField {
+        existing(id, name, FieldType::Text, InputType::Anything, usage, &[])
+    }
+
+    fn orphan_ids(plan: &SyncPlan) -> Vec<i64> {
+        plan.actions.iter()
+            .filter_map(|action| match action {
+                PlanAction::Orphan(existing) => Some(existing.id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn names_match_with_and_without_the_cf_prefix() {
+        let plan = SyncPlan::build(
+            &[text("Region", Entity::Ticket), text("cfowner", Entity::Ticket)],
+            &[existing_text(1, "CFRegion", 1), existing_text(2, "CFOwner", 1)],
+            false,
+        );
+        assert!(plan.actions.iter().all(|action| !matches!(action, PlanAction::Create(_) | PlanAction::Orphan(_))));
+    }
+
+    #[test]
+    fn type_and_usage_mismatches_are_conflicts() {
+        let plan = SyncPlan::build(
+            &[text("Region", Entity::Ticket), text("Owner", Entity::User)],
+            &[
+                existing(1, "CFRegion", FieldType::Memo, InputType::Anything, 1, &[]),
+                existing_text(2, "CFOwner", 4),
+            ],
+            false,
+        );
+        let reasons: Vec<&str> = plan.actions.iter()
+            .filter_map(|action| match action {
+                PlanAction::Conflict { reason, .. } => Some(reason.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reasons, ["type: 1 → Text (0)".to_string(), "usage: Clients (4) → Users (3)".to_string()]);
+        assert!(!plan.has_changes());
+    }
+
+    #[test]
+    fn orphans_are_limited_to_the_entities_of_the_input_file() {
+        let plan = SyncPlan::build(
+            &[text("Region", Entity::Ticket)],
+            &[
+                existing_text(1, "CFRegion", 1),
+                existing_text(2, "CFPriority", 1),
+                existing_text(3, "CFLegacy", 0),  // usage 0 is a ticket field
+                existing_text(4, "CFDepartment", 3),
+            ],
+            false,
+        );
+        assert_eq!(orphan_ids(&plan), [2, 3]);
+    }
+
+    #[test]
+    fn fields_of_unknown_usage_never_become_orphans() {
+        let plan = SyncPlan::build(
+            &[text("Region", Entity::Ticket)],
+            &[existing_text(1, "CFRegion", 1), existing_text(2, "CFMystery", 42)],
+            true,
+        );
+        assert!(orphan_ids(&plan).is_empty());
+        assert!(plan.deletions().is_empty());
+    }
+
+    #[test]
+    fn keep_orphans_leaves_them_reported_but_not_deleted() {
+        let mut plan = SyncPlan::build(
+            &[text("Region", Entity::Ticket)],
+            &[existing_text(1, "CFRegion", 1), existing_text(2, "CFPriority", 1)],
+            true,
+        );
+        assert_eq!(plan.deletions().len(), 1);
+        assert!(plan.has_changes());
+
+        plan.keep_orphans();
+        assert!(plan.deletions().is_empty());
+        assert!(!plan.has_changes());
+        assert_eq!(orphan_ids(&plan), [2]);
+    }
+
+    #[test]
+    fn tree_dropdown_options_are_left_out_of_the_diff() {
+        let tree = field("Category", FieldType::SingleSelection, InputType::TreeDropdown, "Hardware,Software", Entity::Ticket);
+        let plan = SyncPlan::build(
+            &[tree],
+            &[existing(1, "CFCategory", FieldType::SingleSelection, InputType::TreeDropdown, 1, &["Laptop", "Printer"])],
+            false,
+        );
+        assert!(matches!(plan.actions[0], PlanAction::NoOp(_, 1)));
+
+        let dropdown = field("Size", FieldType::SingleSelection, InputType::StandardDropdown, "Small,Large", Entity::Ticket);
+        let plan = SyncPlan::build(
+            &[dropdown],
+            &[existing(2, "CFSize", FieldType::SingleSelection, InputType::StandardDropdown, 1, &["Small"])],
+            false,
+        );
+        assert!(matches!(&plan.actions[0], PlanAction::Update { changes, .. } if changes == &["options: 'Small' → 'Small,Large'"]));
+    }
+}
@@ -3,6 +3,8 @@ use serde::Serialize;
 
 #[derive(Serialize)]
 pub struct FieldJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i64>,
     usage: u8,
     name: String,
     label: String,
@@ -24,24 +26,33 @@ impl JsonTransformer {
         fields
             .iter()
             .map(|field| FieldJson {
-                usage: 1, // Default value
+                id: None,
+                usage: field.usage.id(),
                 name: field.name.clone(),
                 label: field.label.clone(),
-                type_id: field.type_id.to_string(),
-                inputtype: field.input_type_id.to_string(),
-                new_values: field.options.clone(),
-                searchable: true,          // Default value
-                user_searchable: true,     // Default value
-                calendar_searchable: true, // Default value
-                copytochild: true,         // Default value
-                copytochildonupdate: true, // Default value
+                type_id: field.field_type.id().to_string(),
+                inputtype: field.input_type.id().to_string(),
+                new_values: field.options.to_string(),
+                searchable: field.flags.searchable,
+                user_searchable: field.flags.user_searchable,
+                calendar_searchable: field.flags.calendar_searchable,
+                copytochild: field.flags.copy_to_child,
+                copytochildonupdate: field.flags.copy_to_child_on_update,
             })
             .collect()
     }
 
-    #[allow(dead_code)]
     pub fn to_json(fields: &[Field]) -> Result<String, serde_json::Error> {
         let json_fields = Self::transform_fields(fields);
         serde_json::to_string_pretty(&json_fields)
     }
+
+    // Halo updates a field when the posted payload carries the id of an existing one
+    pub fn to_update_json(field: &Field, id: i64) -> Result<String, serde_json::Error> {
+        let mut json_fields = Self::transform_fields(std::slice::from_ref(field));
+        for json_field in &mut json_fields {
+            json_field.id = Some(id);
+        }
+        serde_json::to_string_pretty(&json_fields)
+    }
 }
@@ -0,0 +1,61 @@
+use csv::Writer;
+use crate::models::field::Field;
+use crate::error::Result;
+
+/// Column order expected by `CsvSource::get_field_positions`, followed by the optional columns.
+const HEADERS: [&str; 11] = [
+    "name", "label", "type_id", "input_type_id", "options",
+    "usage", "searchable", "user_searchable", "calendar_searchable", "copytochild", "copytochildonupdate",
+];
+
+pub struct CsvWriter;
+
+impl CsvWriter {
+    pub fn new() -> Self {
+        CsvWriter
+    }
+
+    fn row(field: &Field) -> Vec<String> {
+        vec![
+            field.name.clone(),
+            field.label.clone(),
+            field.field_type.id().to_string(),
+            field.input_type.id().to_string(),
+            field.options.to_string(),
+            field.usage.id().to_string(),
+            field.flags.searchable.to_string(),
+            field.flags.user_searchable.to_string(),
+            field.flags.calendar_searchable.to_string(),
+            field.flags.copy_to_child.to_string(),
+            field.flags.copy_to_child_on_update.to_string(),
+        ]
+    }
+
+    pub fn write_fields(&self, path: &str, fields: &[Field]) -> Result<()> {
+        let mut writer = Writer::from_path(path)?;
+        writer.write_record(HEADERS)?;
+
+        for field in fields {
+            writer.write_record(Self::row(field))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes fields that failed to import with their error in a last `error` column,
+    /// which the reader ignores so the file can be fixed and imported again.
+    pub fn write_failed(&self, path: &str, failed: &[(&Field, &str)]) -> Result<()> {
+        let mut writer = Writer::from_path(path)?;
+        writer.write_record(HEADERS.iter().chain(&["error"]))?;
+
+        for (field, error) in failed {
+            let mut row = Self::row(field);
+            row.push(error.to_string());
+            writer.write_record(row)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}